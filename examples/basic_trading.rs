@@ -36,8 +36,8 @@ cargo run --example basic_trading
 */
 
 use r_fubon_neo::{
-    FubonSDK, CoreSDK, Order, OrderType, BSAction, TimeInForce,
-    Error, Result, LoginCredentials
+    FubonSDK, CoreSDK, Order, OrderType, BSAction, TimeInForce, TradingType,
+    Error, Result, LoginCredentials, Decimal, MarketSession
 };
 use std::env;
 
@@ -79,20 +79,28 @@ async fn main() -> Result<()> {
     
     println!("🔐 執行登入...");
     let accounts = sdk.login(credentials)
-        .map_err(|e| Error::general(&format!("登入失敗: {}", e)))?;
+        .map_err(|e| Error::general(format!("登入失敗: {}", e)))?;
     
     println!("✅ 登入成功! 找到 {} 個帳戶:", accounts.len());
     for account in &accounts {
         println!("  - {} ({}): {}", account.account_name, account.account_id, account.account_type);
     }
     
+    let account = accounts.iter()
+        .find(|account| account.is_stock())
+        .ok_or_else(|| Error::general("找不到證券帳戶"))?;
+    
     // 1. 檢查帳戶餘額
     println!("\n💰 檢查帳戶餘額...");
-    match sdk.get_account_balance() {
+    match sdk.get_account_balance(account) {
         Ok(balance) => {
-            println!("帳戶餘額: ${:.2}", balance);
+            println!("銀行餘額: ${:.2}", balance.bank_balance);
+            println!("今日可用額度: ${:.2}", balance.buying_power);
+            for settlement in balance.settlements() {
+                println!("  {} 交割款: ${:.2}", settlement.date, settlement.amount);
+            }
             
-            if balance < 100000.0 {
+            if !balance.covers(Decimal::from(100000)) {
                 println!("⚠️  餘額可能不足以進行交易");
             }
         }
@@ -104,14 +112,23 @@ async fn main() -> Result<()> {
     
     // 2. 查看目前持倉
     println!("\n📊 查看目前持倉...");
-    match sdk.get_positions() {
+    match sdk.get_positions(account) {
         Ok(positions) => {
             if positions.is_empty() {
                 println!("目前無持倉");
             } else {
                 println!("目前持倉:");
-                for symbol in &positions {
-                    println!("  - {}", symbol);
+                for position in &positions {
+                    println!(
+                        "  - {} {:?} {} 股 ({} 張), 均價 {:.2}, 現價 {:.2}, 未實現損益 {:.0}",
+                        position.symbol,
+                        position.trading_type,
+                        position.quantity,
+                        position.lots(),
+                        position.avg_cost,
+                        position.last_price,
+                        position.unrealized_pnl
+                    );
                 }
             }
         }
@@ -126,10 +143,13 @@ async fn main() -> Result<()> {
     let order = Order {
         symbol: "2330".to_string(),    // 台積電
         quantity: 1000,                // 1張 (1000股)
-        price: Some(500.0),            // 限價 500 元
+        price: Some(Decimal::from(500)),            // 限價 500 元
+        stop_price: None,              // 非停損單
         order_type: OrderType::Limit,  // 限價單
         time_in_force: TimeInForce::Day, // 當日有效
         action: BSAction::Buy,         // 買入
+        trading_type: TradingType::Normal, // 現股
+        session: MarketSession::Regular, // 整股盤
     };
     
     println!("訂單詳情:");
@@ -143,16 +163,18 @@ async fn main() -> Result<()> {
     // 確認是否要送出訂單
     println!("\n⚠️  這是模擬訂單，實際上不會送出真實交易");
     
-    match sdk.place_order(&order) {
-        Ok(order_id) => {
+    match sdk.place_order(account, &order) {
+        Ok(result) => {
             println!("✅ 訂單送出成功!");
-            println!("訂單 ID: {}", order_id);
+            println!("委託書號: {}", result.order_no);
+            println!("委託序號: {}", result.seq_no);
             
             // 4. 查詢訂單狀態
             println!("\n🔍 查詢訂單狀態...");
-            match sdk.get_order_status(&order_id) {
+            match sdk.get_order_status(account, &result.order_no) {
                 Ok(status) => {
-                    println!("訂單狀態: {}", status);
+                    println!("訂單狀態: {:?}", status.status);
+                    println!("已成交: {} / 未成交: {}", status.filled_quantity, status.remaining_quantity);
                 }
                 Err(e) => {
                     eprintln!("❌ 查詢訂單狀態失敗: {}", e);
@@ -161,7 +183,7 @@ async fn main() -> Result<()> {
             
             // 5. 模擬取消訂單 (僅作示範)
             println!("\n❌ 模擬取消訂單...");
-            match sdk.cancel_order(&order_id) {
+            match sdk.cancel_order(account, &result.order_no) {
                 Ok(_) => {
                     println!("✅ 訂單取消成功");
                 }
@@ -193,15 +215,18 @@ mod tests {
         let order = Order {
             symbol: "2330".to_string(),
             quantity: 1000,
-            price: Some(500.0),
+            price: Some(Decimal::from(500)),
+            stop_price: None,
             order_type: OrderType::Limit,
             time_in_force: TimeInForce::Day,
             action: BSAction::Buy,
+            trading_type: TradingType::Normal,
+            session: MarketSession::Regular,
         };
         
         assert_eq!(order.symbol, "2330");
         assert_eq!(order.quantity, 1000);
-        assert_eq!(order.price, Some(500.0));
+        assert_eq!(order.price, Some(Decimal::from(500)));
     }
     
     #[test]
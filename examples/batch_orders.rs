@@ -0,0 +1,164 @@
+/*!
+# 批次下單與刪單範例 (P.O.C)
+
+⚠️ **P.O.C 專案**: 本範例僅供概念驗證和學習使用，不適用於實際交易。
+👨‍💻 **開發者**: Steve Lo (info@sd.idv.tw)
+
+本範例以記憶體後端示範 `place_orders` 與 `cancel_orders`：批次中的委託
+依 `ThrottleConfig` 限制同時送出的數量與每秒委託數，並逐筆回傳結果，
+單筆失敗不影響其他委託。
+
+## 功能
+- 設定同時送出數量與每秒委託上限
+- 批次下單、批次刪單
+- 逐筆檢查結果
+
+## 使用方法
+```bash
+cargo run --example batch_orders --features memory
+cargo test --example batch_orders --features memory
+```
+*/
+
+use std::sync::Arc;
+use std::time::Duration;
+use r_fubon_neo::backend::MemoryBackend;
+use r_fubon_neo::{
+    Account, AsyncCoreSDK, BSAction, Decimal, FubonSDK, LoginCredentials, Order, Result, ThrottleConfig,
+};
+
+fn credentials() -> LoginCredentials {
+    LoginCredentials {
+        personal_id: "A123456789".to_string(),
+        password: "secret".to_string(),
+        cert_path: "/path/to/cert.p12".to_string(),
+        cert_pass: None,
+    }
+}
+
+/// 登入並回傳 SDK 與股票帳戶
+async fn login(backend: MemoryBackend, throttle: ThrottleConfig) -> Result<(FubonSDK, Account)> {
+    let mut sdk = FubonSDK::with_backend(Arc::new(backend)).with_throttle(throttle);
+    let accounts = AsyncCoreSDK::login(&mut sdk, credentials()).await?;
+    Ok((sdk, accounts[0].clone()))
+}
+
+/// 一籃子限價買單
+fn basket(count: usize) -> Vec<Order> {
+    (0..count)
+        .map(|i| {
+            Order::builder(format!("{}", 2881 + i), BSAction::Buy, 1000)
+                .with_price(Decimal::from(50))
+                .build()
+                .expect("valid order")
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🧺 批次下單與刪單範例");
+
+    // 最多同時 4 筆、每秒 5 筆，模擬 50ms 的券商往返時間
+    let throttle = ThrottleConfig::new().with_max_concurrency(4)?.with_rate_limit(5)?;
+    let backend = MemoryBackend::new().with_latency(Duration::from_millis(50));
+    let (sdk, account) = login(backend, throttle).await?;
+
+    let started = std::time::Instant::now();
+    let results = AsyncCoreSDK::place_orders(&sdk, &account, &basket(6)).await?;
+    println!("⏱️ 6 筆委託耗時 {:?}", started.elapsed());
+
+    let mut order_ids = Vec::new();
+    for result in &results {
+        match result {
+            Ok(order) => {
+                println!("  ✅ {} {}", order.order_no, order.request.symbol());
+                order_ids.push(order.order_no.clone());
+            }
+            Err(e) => println!("  ❌ {}", e),
+        }
+    }
+
+    let ids: Vec<&str> = order_ids.iter().map(String::as_str).collect();
+    let cancelled = AsyncCoreSDK::cancel_orders(&sdk, &account, &ids).await?;
+    println!("🗑️ 刪單成功 {} 筆", cancelled.iter().filter(|result| result.is_ok()).count());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use r_fubon_neo::Error;
+    use tokio::time::Instant;
+
+    #[test]
+    fn test_zero_limits_are_rejected() {
+        let result = ThrottleConfig::new().with_max_concurrency(0);
+        assert!(matches!(result, Err(Error::InvalidParameter { ref name, .. }) if name == "max_concurrency"));
+
+        let result = ThrottleConfig::new().with_rate_limit(0);
+        assert!(matches!(result, Err(Error::InvalidParameter { ref name, .. }) if name == "orders_per_second"));
+
+        let config = ThrottleConfig::new().with_max_concurrency(3).unwrap().with_rate_limit(7).unwrap();
+        assert_eq!(config.max_concurrency(), 3);
+        assert_eq!(config.max_orders_per_second(), Some(7));
+        assert_eq!(config.without_rate_limit().max_orders_per_second(), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit_spaces_orders() {
+        let throttle = ThrottleConfig::new().with_rate_limit(10).unwrap();
+        let (sdk, account) = login(MemoryBackend::new(), throttle).await.unwrap();
+
+        // Slots at 0, 100, 200, 300 and 400 ms
+        let started = Instant::now();
+        let results = AsyncCoreSDK::place_orders(&sdk, &account, &basket(5)).await.unwrap();
+        let elapsed = started.elapsed();
+
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert!(elapsed >= Duration::from_millis(400), "took {:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(500), "took {:?}", elapsed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_max_concurrency_bounds_batches() {
+        let elapsed_with = |max_concurrency| async move {
+            let throttle = ThrottleConfig::new()
+                .with_max_concurrency(max_concurrency)
+                .unwrap()
+                .without_rate_limit();
+            let backend = MemoryBackend::new().with_latency(Duration::from_millis(100));
+            let (sdk, account) = login(backend, throttle).await.unwrap();
+
+            let started = Instant::now();
+            let results = AsyncCoreSDK::place_orders(&sdk, &account, &basket(6)).await.unwrap();
+            assert!(results.iter().all(|result| result.is_ok()));
+            started.elapsed()
+        };
+
+        // Six 100 ms round trips, two or six at a time
+        let two = elapsed_with(2).await;
+        assert!(two >= Duration::from_millis(300) && two < Duration::from_millis(400), "took {:?}", two);
+        let six = elapsed_with(6).await;
+        assert!(six >= Duration::from_millis(100) && six < Duration::from_millis(200), "took {:?}", six);
+    }
+
+    #[tokio::test]
+    async fn test_each_order_gets_its_own_result() {
+        let (sdk, account) = login(MemoryBackend::new(), ThrottleConfig::new()).await.unwrap();
+
+        let mut orders = basket(3);
+        orders[1].price = Some("50.01".parse().unwrap());
+        let results = AsyncCoreSDK::place_orders(&sdk, &account, &orders).await.unwrap();
+
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(Error::InvalidPrice { .. })));
+        assert!(results[2].is_ok());
+
+        let placed = results[0].as_ref().unwrap().order_no.clone();
+        let cancelled = AsyncCoreSDK::cancel_orders(&sdk, &account, &[placed.as_str(), "missing"]).await.unwrap();
+        assert!(cancelled[0].is_ok());
+        assert!(cancelled[1].is_err());
+    }
+}
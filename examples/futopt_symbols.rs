@@ -0,0 +1,138 @@
+/*!
+# 期貨/選擇權商品代號範例 (P.O.C)
+
+⚠️ **P.O.C 專案**: 本範例僅供概念驗證和學習使用，不適用於實際交易。
+👨‍💻 **開發者**: Steve Lo (info@sd.idv.tw)
+
+本範例展示如何將期交所商品代號 (例如 `TXFK4`、`MXFA5`、`TXO20000L4`)
+解析為 `ContractSpec`，並由 `ContractSpec` 組回商品代號。
+
+## 功能
+- 解析期貨、月選擇權與週選擇權代號
+- 月份代碼 (期貨/買權 A-L，賣權 M-X)
+- 由合約規格組出下單用的商品代號
+
+## 使用方法
+```bash
+cargo run --example futopt_symbols
+```
+*/
+
+use r_fubon_neo::{BSAction, CallPut, ContractSpec, Decimal, FutOptOrder, Result};
+
+fn main() -> Result<()> {
+    println!("📜 期貨/選擇權商品代號範例");
+
+    // 1. 解析商品代號
+    for symbol in ["TXFK4", "MXFA5", "TXO20000L4", "TXO20000X4", "TX121500C5"] {
+        let spec = ContractSpec::parse(symbol)?;
+        println!(
+            "  {:<12} 商品 {} ({}) {}/{:02} 履約價 {} {}{}",
+            symbol,
+            spec.product,
+            spec.market_type(),
+            spec.year,
+            spec.month,
+            spec.strike.map_or("-".to_string(), |strike| strike.to_string()),
+            spec.call_put.map_or("-".to_string(), |call_put| call_put.to_string()),
+            spec.week().map_or(String::new(), |week| format!(" 第{}週", week)),
+        );
+    }
+
+    // 2. 由合約規格組出代號並下單
+    let put = ContractSpec::option("TXO", 2025, 3, Decimal::from(21000), CallPut::Put)?;
+    let order = FutOptOrder::builder(put.symbol(), BSAction::Buy, 1)
+        .with_price(Decimal::from(85))
+        .build()?;
+    println!("\n📝 賣權代號: {} -> {:?}", put, order.contract()?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use r_fubon_neo::contract::{month_code, parse_month_code};
+    use r_fubon_neo::FutOptMarketType;
+
+    fn parse(symbol: &str) -> ContractSpec {
+        let reference = NaiveDate::from_ymd_opt(2024, 10, 1).unwrap();
+        ContractSpec::parse_at(symbol, reference).unwrap()
+    }
+
+    #[test]
+    fn test_futures() {
+        let spec = parse("TXFK4");
+        assert_eq!((spec.product.as_str(), spec.year, spec.month), ("TXF", 2024, 11));
+        assert_eq!(spec.market_type(), FutOptMarketType::Future);
+        assert_eq!((spec.strike, spec.call_put), (None, None));
+
+        let spec = parse("MXFA5");
+        assert_eq!((spec.product.as_str(), spec.year, spec.month), ("MXF", 2025, 1));
+        assert_eq!(spec, ContractSpec::future("MXF", 2025, 1).unwrap());
+    }
+
+    #[test]
+    fn test_options() {
+        let call = parse("TXO20000L4");
+        assert_eq!((call.product.as_str(), call.year, call.month), ("TXO", 2024, 12));
+        assert_eq!(call.strike, Some(Decimal::from(20000)));
+        assert_eq!(call.call_put, Some(CallPut::Call));
+        assert!(!call.is_weekly());
+
+        let put = parse("TXO20000X4");
+        assert_eq!((put.month, put.call_put), (12, Some(CallPut::Put)));
+    }
+
+    #[test]
+    fn test_weekly_options() {
+        let spec = parse("TX121500C5");
+        assert_eq!(spec.week(), Some(1));
+        assert_eq!(spec.monthly_product(), "TXO");
+        assert_eq!((spec.year, spec.month, spec.call_put), (2025, 3, Some(CallPut::Call)));
+        assert_eq!(parse("TXO21500C5").week(), None);
+    }
+
+    #[test]
+    fn test_month_codes() {
+        assert_eq!(month_code(1, None), Some('A'));
+        assert_eq!(month_code(12, Some(CallPut::Call)), Some('L'));
+        assert_eq!(month_code(1, Some(CallPut::Put)), Some('M'));
+        assert_eq!(month_code(12, Some(CallPut::Put)), Some('X'));
+        assert_eq!(month_code(13, None), None);
+        for month in 1..=12 {
+            for call_put in [None, Some(CallPut::Put)] {
+                assert_eq!(parse_month_code(month_code(month, call_put).unwrap()), Some((month, call_put)));
+            }
+        }
+        assert_eq!(parse_month_code('Y'), None);
+    }
+
+    #[test]
+    fn test_formats_back() {
+        for symbol in ["TXFK4", "MXFA5", "TXO20000L4", "TXO20000X4", "TX121500C5"] {
+            assert_eq!(parse(symbol).to_string(), symbol);
+        }
+        let put = ContractSpec::option("TXO", 2025, 3, Decimal::from(21000), CallPut::Put).unwrap();
+        assert_eq!(put.symbol(), "TXO21000O5");
+    }
+
+    #[test]
+    fn test_year_digit_window() {
+        let reference = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        assert_eq!(ContractSpec::parse_at("TXFA1", reference).unwrap().year, 2021);
+        assert_eq!(ContractSpec::parse_at("TXFA0", reference).unwrap().year, 2030);
+        assert_eq!(ContractSpec::parse_at("TXFA6", reference).unwrap().year, 2026);
+    }
+
+    #[test]
+    fn test_invalid_symbols() {
+        let reference = NaiveDate::from_ymd_opt(2024, 10, 1).unwrap();
+        for symbol in ["", "TXF", "TXFK", "TXFZ4", "TXFXA", "TXFX4", "txfk4", "TXO2O000L4", "臺指K4", "TXO0L4", "TXO020000L4"] {
+            assert!(ContractSpec::parse_at(symbol, reference).is_err(), "{} should not parse", symbol);
+        }
+        assert!(ContractSpec::future("TXF", 2024, 13).is_err());
+        assert!(ContractSpec::option("TXO", 2024, 12, Decimal::new(205, 1), CallPut::Call).is_err());
+    }
+}
@@ -318,7 +318,7 @@ async fn main() -> Result<()> {
     
     println!("🔐 執行登入...");
     let accounts = sdk.login(credentials)
-        .map_err(|e| Error::general(&format!("登入失敗: {}", e)))?;
+        .map_err(|e| Error::general(format!("登入失敗: {}", e)))?;
     
     println!("✅ 登入成功! 找到 {} 個帳戶", accounts.len());
     
@@ -242,7 +242,7 @@ async fn main() -> Result<()> {
     
     println!("🔐 執行登入...");
     let accounts = sdk.login(credentials)
-        .map_err(|e| Error::general(&format!("登入失敗: {}", e)))?;
+        .map_err(|e| Error::general(format!("登入失敗: {}", e)))?;
     
     println!("✅ 登入成功! 找到 {} 個帳戶", accounts.len());
     
@@ -318,7 +318,7 @@ async fn main() -> Result<()> {
         ctrlc::set_handler(move || {
             println!("\n🛑 收到關閉訊號，正在優雅關閉...");
             r.store(false, std::sync::atomic::Ordering::SeqCst);
-        }).map_err(|e| Error::general(&format!("設置信號處理器失敗: {}", e)))?;
+        }).map_err(|e| Error::general(format!("設置信號處理器失敗: {}", e)))?;
         
         // 主循環
         let mut counter = 0;
@@ -0,0 +1,329 @@
+/*!
+# 委託單建構器範例 (P.O.C)
+
+⚠️ **P.O.C 專案**: 本範例僅供概念驗證和學習使用，不適用於實際交易。
+👨‍💻 **開發者**: Steve Lo (info@sd.idv.tw)
+
+本範例展示如何使用建構器建立經過檢查的委託單，
+不合理的欄位組合會在送單前回傳 `Error::InvalidOrder`。
+
+## 功能
+- 建立股票限價、市價、停損限價委託
+- 建立盤中零股委託
+- 建立期貨/選擇權委託
+- 建立條件單 (股票與期貨/選擇權)
+- 顯示不合法組合的錯誤訊息
+
+## 使用方法
+```bash
+cargo run --example order_builders
+```
+*/
+
+use r_fubon_neo::{
+    BSAction, Condition, ConditionOrder, ConditionOrderType, Decimal, FutOptConditionOrder,
+    FutOptOrder, FutOptOrderType, MarketSession, Operator, Order, OrderType, Result, TimeInForce,
+    TriggerContent,
+};
+
+fn main() -> Result<()> {
+    println!("🧱 委託單建構器範例");
+
+    // 1. 股票限價單 (預設: 限價、ROD、現股、整股盤)
+    let limit = Order::builder("2330", BSAction::Buy, 1000)
+        .with_price(Decimal::from(500))
+        .build()?;
+    println!("✅ 限價單: {:?}", limit);
+
+    // 2. 股票市價 IOC 單
+    let market = Order::builder("2330", BSAction::Sell, 2000)
+        .with_order_type(OrderType::Market)
+        .with_time_in_force(TimeInForce::Ioc)
+        .build()?;
+    println!("✅ 市價單: {:?}", market);
+
+    // 3. 停損限價單: 觸價 510 後以 512 限價買進
+    let stop_limit = Order::builder("2330", BSAction::Buy, 1000)
+        .with_order_type(OrderType::StopLimit)
+        .with_stop_price(Decimal::from(510))
+        .with_price(Decimal::from(512))
+        .build()?;
+    println!("✅ 停損限價單: {:?}", stop_limit);
+
+    // 4. 盤中零股 (股數須少於一張)
+    let odd_lot = Order::builder("2330", BSAction::Buy, 50)
+        .with_price(Decimal::from(500))
+        .with_session(MarketSession::IntradayOddLot)
+        .build()?;
+    println!("✅ 盤中零股: {:?}", odd_lot);
+
+    // 5. 期貨限價單
+    let futures = FutOptOrder::builder("TXFK4", BSAction::Buy, 1)
+        .with_order_type(FutOptOrderType::Limit)
+        .with_price(Decimal::from(22000))
+        .build()?;
+    println!("✅ 期貨單: {:?}", futures);
+
+    // 6. 條件單: 台積電成交價 >= 520 時送出市價買單
+    let condition = Condition {
+        symbol: "2330".to_string(),
+        operator: Operator::GreaterEqual,
+        value: Decimal::from(520),
+        trigger_content: TriggerContent::Price,
+    };
+    let market_buy = Order::builder("2330", BSAction::Buy, 1000)
+        .with_order_type(OrderType::Market)
+        .build()?;
+    let condition_order = ConditionOrder::builder(ConditionOrderType::Stop, condition, market_buy).build()?;
+    println!("✅ 條件單: {:?}", condition_order);
+
+    // 7. 期貨條件單: 台指期成交價 <= 21500 時送出市價賣單
+    let futopt_condition = Condition {
+        symbol: "TXFK4".to_string(),
+        operator: Operator::LessEqual,
+        value: Decimal::from(21500),
+        trigger_content: TriggerContent::Price,
+    };
+    let market_sell = FutOptOrder::builder("TXFK4", BSAction::Sell, 1)
+        .with_order_type(FutOptOrderType::Market)
+        .build()?;
+    let futopt_condition_order = FutOptConditionOrder::builder(futopt_condition, market_sell).build()?;
+    println!("✅ 期貨條件單: {:?}", futopt_condition_order);
+
+    // 8. 不合法的組合
+    println!("\n❌ 不合法的組合:");
+    let invalid = [
+        Order::builder("2330", BSAction::Buy, 1000)
+            .with_order_type(OrderType::Market)
+            .with_price(Decimal::from(500))
+            .build(),
+        Order::builder("2330", BSAction::Buy, 1000)
+            .with_order_type(OrderType::StopLimit)
+            .with_price(Decimal::from(512))
+            .build(),
+        Order::builder("2330", BSAction::Buy, 50)
+            .with_price(Decimal::from(500))
+            .build(),
+    ];
+    for result in invalid {
+        if let Err(e) = result {
+            println!("  {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use r_fubon_neo::{Error, OrderError};
+
+    fn order(order_type: OrderType) -> r_fubon_neo::OrderBuilder {
+        Order::builder("2330", BSAction::Buy, 1000).with_order_type(order_type)
+    }
+
+    fn order_error(result: Result<impl std::fmt::Debug>) -> OrderError {
+        match result {
+            Err(Error::InvalidOrder(e)) => e,
+            other => panic!("expected an invalid order, got {:?}", other),
+        }
+    }
+
+    fn condition(value: Decimal) -> Condition {
+        Condition {
+            symbol: "2330".to_string(),
+            operator: Operator::GreaterEqual,
+            value,
+            trigger_content: TriggerContent::Price,
+        }
+    }
+
+    #[test]
+    fn test_valid_orders() {
+        assert!(order(OrderType::Limit).with_price(Decimal::from(500)).build().is_ok());
+        assert!(order(OrderType::Market).with_time_in_force(TimeInForce::Fok).build().is_ok());
+        assert!(order(OrderType::Stop).with_stop_price(Decimal::from(510)).build().is_ok());
+        assert!(order(OrderType::StopLimit)
+            .with_stop_price(Decimal::from(510))
+            .with_price(Decimal::from(512))
+            .with_time_in_force(TimeInForce::Gtc)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_invalid_field_combinations() {
+        // Empty symbol and zero quantity
+        assert_eq!(order_error(Order::builder("", BSAction::Buy, 1000).with_price(Decimal::ONE).build()), OrderError::EmptySymbol);
+        assert_eq!(order_error(Order::builder("2330", BSAction::Buy, 0).with_price(Decimal::ONE).build()), OrderError::ZeroQuantity);
+
+        // Market orders with a price or a stop price
+        assert_eq!(
+            order_error(order(OrderType::Market).with_price(Decimal::from(500)).build()),
+            OrderError::UnexpectedPrice { order_type: "Market" }
+        );
+        assert_eq!(
+            order_error(order(OrderType::Market).with_stop_price(Decimal::from(500)).build()),
+            OrderError::UnexpectedStopPrice { order_type: "Market" }
+        );
+
+        // Limit orders without a price or with a stop price
+        assert_eq!(order_error(order(OrderType::Limit).build()), OrderError::MissingPrice { order_type: "Limit" });
+        assert_eq!(
+            order_error(order(OrderType::Limit).with_price(Decimal::from(500)).with_stop_price(Decimal::from(490)).build()),
+            OrderError::UnexpectedStopPrice { order_type: "Limit" }
+        );
+
+        // Stop orders without a stop price or with a limit price
+        assert_eq!(order_error(order(OrderType::Stop).build()), OrderError::MissingStopPrice { order_type: "Stop" });
+        assert_eq!(
+            order_error(order(OrderType::Stop).with_stop_price(Decimal::from(510)).with_price(Decimal::from(512)).build()),
+            OrderError::UnexpectedPrice { order_type: "Stop" }
+        );
+
+        // Stop-limit orders missing either price
+        assert_eq!(
+            order_error(order(OrderType::StopLimit).with_price(Decimal::from(512)).build()),
+            OrderError::MissingStopPrice { order_type: "StopLimit" }
+        );
+        assert_eq!(
+            order_error(order(OrderType::StopLimit).with_stop_price(Decimal::from(510)).build()),
+            OrderError::MissingPrice { order_type: "StopLimit" }
+        );
+    }
+
+    #[test]
+    fn test_invalid_time_in_force() {
+        // Market orders cannot rest until cancelled
+        assert_eq!(
+            order_error(order(OrderType::Market).with_time_in_force(TimeInForce::Gtc).build()),
+            OrderError::TimeInForceNotAllowed { order_type: "Market", time_in_force: TimeInForce::Gtc }
+        );
+
+        // Stop orders wait for their trigger, so cannot be immediate
+        for time_in_force in [TimeInForce::Ioc, TimeInForce::Fok] {
+            assert_eq!(
+                order_error(order(OrderType::Stop).with_stop_price(Decimal::from(510)).with_time_in_force(time_in_force).build()),
+                OrderError::TimeInForceNotAllowed { order_type: "Stop", time_in_force }
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_session_combinations() {
+        let session_error = |builder: r_fubon_neo::OrderBuilder| {
+            matches!(builder.build(), Err(Error::InvalidSession { .. }))
+        };
+
+        // Odd lots in the regular session, and whole lots in an odd-lot session
+        assert!(session_error(Order::builder("2330", BSAction::Buy, 50).with_price(Decimal::from(500))));
+        assert!(session_error(order(OrderType::Limit).with_price(Decimal::from(500)).with_session(MarketSession::IntradayOddLot)));
+
+        // Odd-lot and fixed price sessions take ROD limit orders only
+        let odd_lot = Order::builder("2330", BSAction::Buy, 50).with_session(MarketSession::AfterHoursOddLot);
+        assert!(session_error(odd_lot.clone().with_price(Decimal::from(500)).with_time_in_force(TimeInForce::Ioc)));
+        assert!(session_error(odd_lot.with_order_type(OrderType::Market)));
+        assert!(session_error(order(OrderType::Market).with_session(MarketSession::AfterHoursFixedPrice)));
+    }
+
+    #[test]
+    fn test_invalid_futopt_orders() {
+        let futopt = |order_type| FutOptOrder::builder("TXFK4", BSAction::Sell, 1).with_order_type(order_type);
+
+        assert_eq!(
+            order_error(futopt(FutOptOrderType::Market).with_price(Decimal::from(22000)).build()),
+            OrderError::UnexpectedPrice { order_type: "Market" }
+        );
+        assert_eq!(
+            order_error(futopt(FutOptOrderType::StopLimit).with_price(Decimal::from(22000)).build()),
+            OrderError::MissingStopPrice { order_type: "StopLimit" }
+        );
+        assert!(futopt(FutOptOrderType::Stop).with_stop_price(Decimal::from(21900)).build().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_condition_orders() {
+        let market = order(OrderType::Market).build().unwrap();
+        let limit = order(OrderType::Limit).with_price(Decimal::from(500)).build().unwrap();
+
+        // Condition must name a symbol and a positive trigger value
+        let mut no_symbol = condition(Decimal::from(520));
+        no_symbol.symbol.clear();
+        assert_eq!(
+            order_error(ConditionOrder::builder(ConditionOrderType::Stop, no_symbol, market.clone()).build()),
+            OrderError::EmptyConditionSymbol
+        );
+        assert_eq!(
+            order_error(ConditionOrder::builder(ConditionOrderType::Stop, condition(Decimal::ZERO), market.clone()).build()),
+            OrderError::NonPositiveConditionValue
+        );
+
+        // Stop sends a market order and stop-limit a limit order
+        assert_eq!(
+            order_error(ConditionOrder::builder(ConditionOrderType::Stop, condition(Decimal::from(520)), limit.clone()).build()),
+            OrderError::ConditionOrderTypeMismatch { condition_type: ConditionOrderType::Stop, order_type: "Limit" }
+        );
+        assert_eq!(
+            order_error(ConditionOrder::builder(ConditionOrderType::StopLimit, condition(Decimal::from(520)), market.clone()).build()),
+            OrderError::ConditionOrderTypeMismatch { condition_type: ConditionOrderType::StopLimit, order_type: "Market" }
+        );
+
+        // The triggered order cannot carry its own stop
+        let stop = order(OrderType::Stop).with_stop_price(Decimal::from(510)).build().unwrap();
+        assert_eq!(
+            order_error(ConditionOrder::builder(ConditionOrderType::Oco, condition(Decimal::from(520)), stop).build()),
+            OrderError::ConditionOrderTypeMismatch { condition_type: ConditionOrderType::Oco, order_type: "Stop" }
+        );
+
+        // The order itself is checked too
+        let mut unpriced = limit;
+        unpriced.price = None;
+        assert_eq!(
+            order_error(ConditionOrder::builder(ConditionOrderType::StopLimit, condition(Decimal::from(520)), unpriced).build()),
+            OrderError::MissingPrice { order_type: "Limit" }
+        );
+    }
+
+    #[test]
+    fn test_invalid_futopt_condition_orders() {
+        let futopt = |order_type| FutOptOrder::builder("TXFK4", BSAction::Sell, 1).with_order_type(order_type);
+        let market = futopt(FutOptOrderType::Market).build().unwrap();
+        let trigger = |value| Condition { symbol: "TXFK4".to_string(), ..condition(value) };
+
+        assert!(FutOptConditionOrder::builder(trigger(Decimal::from(21500)), market.clone()).build().is_ok());
+
+        // Condition must name a symbol and a positive trigger value
+        let mut no_symbol = trigger(Decimal::from(21500));
+        no_symbol.symbol.clear();
+        assert_eq!(
+            order_error(FutOptConditionOrder::builder(no_symbol, market.clone()).build()),
+            OrderError::EmptyConditionSymbol
+        );
+        assert_eq!(
+            order_error(FutOptConditionOrder::builder(trigger(Decimal::ZERO), market.clone()).build()),
+            OrderError::NonPositiveConditionValue
+        );
+
+        // The triggered order cannot carry its own stop
+        let stop = futopt(FutOptOrderType::Stop).with_stop_price(Decimal::from(21400)).build().unwrap();
+        assert_eq!(
+            order_error(FutOptConditionOrder::builder(trigger(Decimal::from(21500)), stop).build()),
+            OrderError::FutOptConditionOrderType { order_type: "Stop" }
+        );
+
+        // The order itself is checked too
+        let mut empty = market.clone();
+        empty.quantity = 0;
+        assert_eq!(
+            order_error(FutOptConditionOrder::builder(trigger(Decimal::from(21500)), empty).build()),
+            OrderError::ZeroQuantity
+        );
+        let mut unpriced = futopt(FutOptOrderType::Limit).with_price(Decimal::from(21500)).build().unwrap();
+        unpriced.price = None;
+        assert_eq!(
+            order_error(FutOptConditionOrder::builder(trigger(Decimal::from(21500)), unpriced).build()),
+            OrderError::MissingPrice { order_type: "Limit" }
+        );
+    }
+}
@@ -0,0 +1,322 @@
+/*!
+# 模擬撮合範例 (P.O.C)
+
+⚠️ **P.O.C 專案**: 本範例僅供概念驗證和學習使用，不適用於實際交易。
+👨‍💻 **開發者**: Steve Lo (info@sd.idv.tw)
+
+本範例使用 `PaperBackend` 以重播的五檔與成交資料撮合委託，
+不需要認證資訊，也不會連線券商。
+
+## 功能
+- 重播五檔 (`on_book`) 與成交 (`on_trade`)
+- 價格優先、時間優先撮合，依五檔數量部分成交
+- IOC/FOK 委託、改價後失去時間優先
+- 現金檢查與期貨損益 (依契約乘數換算為新台幣)
+
+## 使用方法
+```bash
+cargo run --example paper_trading --features paper
+cargo test --example paper_trading --features paper
+```
+*/
+
+use r_fubon_neo::backend::paper::{BookLevel, PaperBackend};
+use r_fubon_neo::{Account, Backend, BSAction, Decimal, LoginCredentials, Order, Result};
+
+/// 示範帳戶 (與 MemoryBackend 相同)
+const STOCK_ACCOUNT: &str = "1234567890";
+
+fn credentials() -> LoginCredentials {
+    LoginCredentials {
+        personal_id: "A123456789".to_string(),
+        password: "paper".to_string(),
+        cert_path: String::new(),
+        cert_pass: None,
+    }
+}
+
+/// 登入並回傳股票與期貨帳戶
+async fn login(paper: &PaperBackend) -> Result<(Account, Account)> {
+    let session = paper.login(&credentials()).await?;
+    Ok((session.accounts[0].clone(), session.accounts[1].clone()))
+}
+
+fn level(price: i64, size: u64) -> BookLevel {
+    BookLevel::new(Decimal::from(price), size)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("📒 模擬撮合範例");
+
+    let paper = PaperBackend::new().with_cash(STOCK_ACCOUNT, Decimal::from(1_000_000));
+    let (stock, _) = login(&paper).await?;
+
+    // 1. 重播五檔: 賣方 66 (1 張)、67 (2 張)
+    paper.on_book("2881", vec![level(65, 3)], vec![level(66, 1), level(67, 2)])?;
+
+    // 2. 限價 67 買進 2 張: 66 與 67 各成交 1 張
+    let order = Order::builder("2881", BSAction::Buy, 2000)
+        .with_price(Decimal::from(67))
+        .build()?;
+    let result = paper.place_order(&stock, &order).await?;
+    println!("✅ 委託 {}: {:?}, 成交 {} 股, 均價 {:?}",
+        result.order_no, result.status, result.filled_quantity, result.avg_fill_price);
+
+    // 3. 成交價 68 的持倉損益
+    paper.on_trade("2881", Decimal::from(68), 1)?;
+    for position in paper.get_positions(&stock).await? {
+        println!("📈 {} {} 股, 成本 {}, 未實現損益 {}",
+            position.symbol, position.quantity, position.avg_cost, position.unrealized_pnl);
+    }
+    println!("💰 現金: {}", paper.cash(&stock)?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use chrono::NaiveDate;
+    use r_fubon_neo::{
+        Amount, Error, FillEvent, FutOptOrder, FutOptOrderType, OrderStatus, OrderType, Price,
+        TimeInForce, TradeEventHandler,
+    };
+
+    async fn paper() -> (PaperBackend, Account, Account) {
+        let paper = PaperBackend::new().with_cash(STOCK_ACCOUNT, Decimal::from(10_000_000));
+        let (stock, futures) = login(&paper).await.unwrap();
+        (paper, stock, futures)
+    }
+
+    fn buy(quantity: u32, price: i64) -> Order {
+        Order::builder("2881", BSAction::Buy, quantity)
+            .with_price(Decimal::from(price))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_price_then_time_priority() {
+        let (paper, stock, _) = paper().await;
+        let low = paper.place_order(&stock, &buy(1000, 65)).await.unwrap();
+        let first = paper.place_order(&stock, &buy(1000, 66)).await.unwrap();
+        let second = paper.place_order(&stock, &buy(1000, 66)).await.unwrap();
+
+        // One lot offered at 65: the higher bids go first, in order of placement
+        paper.on_book("2881", vec![], vec![level(65, 1)]).unwrap();
+        let status = |order_no: String| {
+            let (paper, stock) = (&paper, &stock);
+            async move { paper.get_order_status(stock, &order_no).await.unwrap().status }
+        };
+        assert_eq!(status(first.order_no.clone()).await, OrderStatus::Filled);
+        assert_eq!(status(second.order_no.clone()).await, OrderStatus::Working);
+        assert_eq!(status(low.order_no.clone()).await, OrderStatus::Working);
+
+        paper.on_book("2881", vec![], vec![level(65, 1)]).unwrap();
+        assert_eq!(status(second.order_no).await, OrderStatus::Filled);
+        assert_eq!(status(low.order_no).await, OrderStatus::Working);
+    }
+
+    #[tokio::test]
+    async fn test_partial_fill_against_book_depth() {
+        let (paper, stock, _) = paper().await;
+        paper.on_book("2881", vec![], vec![level(66, 1), level(67, 1), level(68, 5)]).unwrap();
+
+        let result = paper.place_order(&stock, &buy(3000, 67)).await.unwrap();
+
+        assert_eq!(result.status, OrderStatus::PartiallyFilled);
+        assert_eq!(result.filled_quantity, 2000);
+        assert_eq!(result.remaining_quantity, 1000);
+        assert_eq!(result.avg_fill_price, Some("66.5".parse::<Price>().unwrap()));
+
+        let from = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2100, 1, 1).unwrap();
+        let fills = paper.get_filled_history(&stock, from, to).await.unwrap();
+        let prices: Vec<Price> = fills.iter().map(|fill| fill.price).collect();
+        assert_eq!(prices, vec![Decimal::from(66), Decimal::from(67)]);
+    }
+
+    #[tokio::test]
+    async fn test_ioc_cancels_the_remainder() {
+        let (paper, stock, _) = paper().await;
+        paper.on_book("2881", vec![], vec![level(66, 1)]).unwrap();
+
+        let order = Order::builder("2881", BSAction::Buy, 3000)
+            .with_price(Decimal::from(66))
+            .with_time_in_force(TimeInForce::Ioc)
+            .build()
+            .unwrap();
+        let result = paper.place_order(&stock, &order).await.unwrap();
+
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert_eq!(result.filled_quantity, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_fok_fills_completely_or_not_at_all() {
+        let (paper, stock, _) = paper().await;
+        paper.on_book("2881", vec![], vec![level(66, 1)]).unwrap();
+
+        let fok = |quantity| Order::builder("2881", BSAction::Buy, quantity)
+            .with_price(Decimal::from(66))
+            .with_time_in_force(TimeInForce::Fok)
+            .build()
+            .unwrap();
+
+        let killed = paper.place_order(&stock, &fok(2000)).await.unwrap();
+        assert_eq!(killed.status, OrderStatus::Cancelled);
+        assert_eq!(killed.filled_quantity, 0);
+
+        let filled = paper.place_order(&stock, &fok(1000)).await.unwrap();
+        assert_eq!(filled.status, OrderStatus::Filled);
+        assert_eq!(filled.filled_quantity, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_market_orders_against_an_empty_book() {
+        let (paper, stock, _) = paper().await;
+        let market = |action| Order::builder("2881", action, 1000)
+            .with_order_type(OrderType::Market)
+            .with_time_in_force(TimeInForce::Ioc)
+            .build()
+            .unwrap();
+
+        // A buy cannot be priced for the cash check
+        let result = paper.place_order(&stock, &market(BSAction::Buy)).await;
+        assert!(matches!(result, Err(Error::Api(ref msg)) if msg.contains("No market price")));
+
+        let sell = paper.place_order(&stock, &market(BSAction::Sell)).await.unwrap();
+        assert_eq!(sell.status, OrderStatus::Cancelled);
+        assert_eq!(sell.filled_quantity, 0);
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_cash_is_rejected() {
+        let paper = PaperBackend::new().with_cash(STOCK_ACCOUNT, Decimal::from(150_000));
+        let (stock, _) = login(&paper).await.unwrap();
+
+        paper.place_order(&stock, &buy(1000, 100)).await.unwrap();
+
+        // The working buy keeps its cash reserved
+        let result = paper.place_order(&stock, &buy(1000, 100)).await;
+        assert!(matches!(result, Err(Error::Api(ref msg)) if msg.contains("Insufficient cash")));
+        assert_eq!(paper.cash(&stock).unwrap(), Decimal::from(150_000));
+    }
+
+    #[tokio::test]
+    async fn test_modify_price_resets_time_priority() {
+        let (paper, stock, _) = paper().await;
+        let repriced = paper.place_order(&stock, &buy(1000, 65)).await.unwrap();
+        let waiting = paper.place_order(&stock, &buy(1000, 66)).await.unwrap();
+
+        let repriced = paper.modify_price(&stock, &repriced, Decimal::from(66)).await.unwrap();
+        paper.on_book("2881", vec![], vec![level(66, 1)]).unwrap();
+
+        let repriced = paper.get_order_status(&stock, &repriced.order_no).await.unwrap();
+        let waiting = paper.get_order_status(&stock, &waiting.order_no).await.unwrap();
+        assert_eq!(waiting.status, OrderStatus::Filled);
+        assert_eq!(repriced.status, OrderStatus::Working);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_orders_are_not_matched() {
+        let (paper, stock, _) = paper().await;
+        let order = paper.place_order(&stock, &buy(1000, 66)).await.unwrap();
+        paper.cancel_order(&stock, &order.order_no).await.unwrap();
+
+        paper.on_book("2881", vec![], vec![level(66, 1)]).unwrap();
+        paper.on_trade("2881", Decimal::from(66), 1).unwrap();
+
+        let order = paper.get_order_status(&stock, &order.order_no).await.unwrap();
+        assert_eq!(order.status, OrderStatus::Cancelled);
+        assert_eq!(order.filled_quantity, 0);
+        assert!(paper.get_positions(&stock).await.unwrap().is_empty());
+    }
+
+    /// Handler reading the backend from inside its fill callback
+    struct ReentrantHandler {
+        paper: Arc<PaperBackend>,
+        account: Account,
+        seen: Mutex<Vec<(Option<Price>, Amount)>>,
+    }
+
+    impl TradeEventHandler for ReentrantHandler {
+        fn on_filled(&self, _event: &FillEvent) {
+            let last = self.paper.last_price("2881");
+            let cash = self.paper.cash(&self.account).unwrap();
+            self.seen.lock().unwrap().push((last, cash));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fill_handlers_can_call_back_into_the_backend() {
+        let paper = Arc::new(PaperBackend::new().with_cash(STOCK_ACCOUNT, Decimal::from(1_000_000)));
+        let (stock, _) = login(&paper).await.unwrap();
+        let handler = Arc::new(ReentrantHandler {
+            paper: paper.clone(),
+            account: stock.clone(),
+            seen: Mutex::new(Vec::new()),
+        });
+        paper.events().add_handler(handler.clone());
+        paper.place_order(&stock, &buy(1000, 66)).await.unwrap();
+
+        // Replay on another thread so that a deadlock fails the test instead of hanging it
+        let (done, finished) = mpsc::channel();
+        let replay = paper.clone();
+        std::thread::spawn(move || {
+            let _ = done.send(replay.on_trade("2881", Decimal::from(66), 1));
+        });
+        let result = finished.recv_timeout(Duration::from_secs(5)).expect("on_trade deadlocked");
+        result.unwrap();
+
+        // The handler sees the account already settled
+        let seen = handler.seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].0, Some(Decimal::from(66)));
+        assert!(seen[0].1 < Decimal::from(1_000_000 - 66_000));
+    }
+
+    #[tokio::test]
+    async fn test_futures_profit_and_loss_in_twd() {
+        let (paper, _, futures) = paper().await;
+        let start = paper.cash(&futures).unwrap();
+        let limit = |action, price| FutOptOrder::builder("TXFK4", action, 1)
+            .with_order_type(FutOptOrderType::Limit)
+            .with_price(Decimal::from(price))
+            .build()
+            .unwrap();
+
+        paper.on_book("TXFK4", vec![], vec![level(22000, 1)]).unwrap();
+        paper.place_futopt_order(&futures, &limit(BSAction::Buy, 22000)).await.unwrap();
+
+        // 5 points at 200 TWD per point
+        paper.on_trade("TXFK4", Decimal::from(22005), 1).unwrap();
+        let equity = paper.get_futopt_equity(&futures).await.unwrap();
+        assert_eq!(equity.unrealized_pnl, Decimal::from(1000));
+        assert_eq!(equity.equity, start + Decimal::from(1000));
+
+        paper.on_book("TXFK4", vec![level(22010, 1)], vec![]).unwrap();
+        paper.place_futopt_order(&futures, &limit(BSAction::Sell, 22010)).await.unwrap();
+        assert_eq!(paper.cash(&futures).unwrap(), start + Decimal::from(2000));
+    }
+
+    #[tokio::test]
+    async fn test_futures_without_a_contract_multiplier() {
+        let order = FutOptOrder::builder("ZZFK4", BSAction::Buy, 1)
+            .with_order_type(FutOptOrderType::Limit)
+            .with_price(Decimal::from(100))
+            .build()
+            .unwrap();
+
+        let (paper, _, futures) = paper().await;
+        let result = paper.place_futopt_order(&futures, &order).await;
+        assert!(matches!(result, Err(Error::Api(ref msg)) if msg.contains("No contract multiplier for ZZF")));
+
+        let paper = paper.with_contract_multiplier("ZZF", Decimal::from(100));
+        paper.place_futopt_order(&futures, &order).await.unwrap();
+    }
+}
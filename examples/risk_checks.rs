@@ -0,0 +1,248 @@
+/*!
+# 下單前風控檢查範例 (P.O.C)
+
+⚠️ **P.O.C 專案**: 本範例僅供概念驗證和學習使用，不適用於實際交易。
+👨‍💻 **開發者**: Steve Lo (info@sd.idv.tw)
+
+本範例以記憶體後端示範 `RiskLimits`：委託在送出前依序檢查
+禁止交易商品、單筆數量、價格帶、單筆與當日金額及持倉上限，
+違反規則時回傳 `Error::RiskRejected` 並指出規則名稱。
+
+## 功能
+- 單筆數量與金額上限
+- 期貨依契約乘數換算為新台幣金額
+- 當日累計金額與重設
+- 持倉上限、禁止交易商品、價格帶
+- 委託未被接受時退回預留的當日金額
+
+## 使用方法
+```bash
+cargo run --example risk_checks --features paper
+cargo test --example risk_checks --features paper
+```
+*/
+
+use std::sync::Arc;
+use r_fubon_neo::backend::MemoryBackend;
+use r_fubon_neo::{
+    Account, AsyncCoreSDK, Backend, BSAction, Decimal, FubonSDK, LoginCredentials, Order, Result,
+    RiskLimits, ThrottleConfig,
+};
+
+fn credentials() -> LoginCredentials {
+    LoginCredentials {
+        personal_id: "A123456789".to_string(),
+        password: "secret".to_string(),
+        cert_path: "/path/to/cert.p12".to_string(),
+        cert_pass: None,
+    }
+}
+
+/// 登入並回傳 SDK 與股票、期貨帳戶
+async fn login(backend: Arc<dyn Backend>, limits: RiskLimits) -> Result<(FubonSDK, Account, Account)> {
+    let mut sdk = FubonSDK::with_backend(backend)
+        .with_risk_limits(limits)
+        .with_throttle(ThrottleConfig::new().without_rate_limit());
+    let accounts = AsyncCoreSDK::login(&mut sdk, credentials()).await?;
+    Ok((sdk, accounts[0].clone(), accounts[1].clone()))
+}
+
+fn buy(symbol: &str, quantity: u32, price: i64) -> Order {
+    Order::builder(symbol, BSAction::Buy, quantity)
+        .with_price(Decimal::from(price))
+        .build()
+        .expect("valid order")
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🛡️ 下單前風控檢查範例");
+
+    let limits = RiskLimits::new()
+        .with_max_order_quantity(5000)
+        .with_max_daily_notional(Decimal::from(1_000_000))
+        .with_restricted_symbols(["2330"]);
+    let (sdk, stock, _) = login(Arc::new(MemoryBackend::new()), limits).await?;
+
+    let orders = [buy("2881", 2000, 66), buy("2881", 6000, 66), buy("2330", 1000, 500), buy("2881", 5000, 180)];
+    for order in &orders {
+        match AsyncCoreSDK::place_order(&sdk, &stock, order).await {
+            Ok(result) => println!("  ✅ {} {} 股 @ {:?}: {}", order.symbol, order.quantity, order.price, result.order_no),
+            Err(e) => println!("  ❌ {} {} 股 @ {:?}: {}", order.symbol, order.quantity, order.price, e),
+        }
+    }
+    println!("💰 今日累計金額: {}", sdk.risk_guard().daily_notional()?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use r_fubon_neo::backend::PaperBackend;
+    use r_fubon_neo::{
+        Error, FutOptOrder, FutOptOrderType, MarketType, OrderRef, OrderResult, OrderType, Position,
+        RiskRule, TradingType,
+    };
+
+    fn rejected_by(result: Result<OrderResult>, expected: RiskRule) -> bool {
+        matches!(result, Err(Error::RiskRejected { rule, .. }) if rule == expected)
+    }
+
+    async fn memory(limits: RiskLimits) -> (FubonSDK, Account, Account) {
+        login(Arc::new(MemoryBackend::new()), limits).await.unwrap()
+    }
+
+    fn futures(symbol: &str, quantity: u32, price: i64) -> FutOptOrder {
+        FutOptOrder::builder(symbol, BSAction::Buy, quantity)
+            .with_order_type(FutOptOrderType::Limit)
+            .with_price(Decimal::from(price))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_max_order_quantity_and_notional() {
+        let limits = RiskLimits::new()
+            .with_max_order_quantity(5000)
+            .with_max_order_notional(Decimal::from(1_000_000));
+        let (sdk, stock, _) = memory(limits).await;
+
+        let result = AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 6000, 66)).await;
+        assert!(rejected_by(result, RiskRule::MaxOrderQuantity));
+
+        let result = AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 5000, 250)).await;
+        assert!(rejected_by(result, RiskRule::MaxOrderNotional));
+
+        // Exactly at the limit is allowed
+        AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 4000, 250)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_futures_notional_uses_the_contract_multiplier() {
+        let limits = RiskLimits::new()
+            .with_max_order_notional(Decimal::from(5_000_000))
+            .with_contract_multiplier("ZZF", Decimal::from(10));
+        let (sdk, _, account) = memory(limits).await;
+
+        // TXF is worth 200 TWD a point: 4.4 million for one contract at 22000
+        AsyncCoreSDK::place_futopt_order(&sdk, &account, &futures("TXFK4", 1, 22000)).await.unwrap();
+        let result = AsyncCoreSDK::place_futopt_order(&sdk, &account, &futures("TXFK4", 2, 22000)).await;
+        assert!(rejected_by(result, RiskRule::MaxOrderNotional));
+
+        AsyncCoreSDK::place_futopt_order(&sdk, &account, &futures("ZZFK4", 100, 4000)).await.unwrap();
+        let result = AsyncCoreSDK::place_futopt_order(&sdk, &account, &futures("YYFK4", 1, 100)).await;
+        assert!(matches!(result, Err(Error::RiskRejected { rule: RiskRule::MaxOrderNotional, ref reason })
+            if reason == "no contract multiplier for YYF"));
+    }
+
+    #[tokio::test]
+    async fn test_daily_notional_accumulates_and_resets() {
+        let limits = RiskLimits::new().with_max_daily_notional(Decimal::from(250_000));
+        let (sdk, stock, _) = memory(limits).await;
+        let guard = sdk.risk_guard();
+
+        AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 1000, 100)).await.unwrap();
+        AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 1000, 100)).await.unwrap();
+        assert_eq!(guard.daily_notional().unwrap(), Decimal::from(200_000));
+
+        let result = AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 1000, 100)).await;
+        assert!(rejected_by(result, RiskRule::MaxDailyNotional));
+        assert_eq!(guard.daily_notional().unwrap(), Decimal::from(200_000));
+
+        guard.reset_daily_notional().unwrap();
+        assert_eq!(guard.daily_notional().unwrap(), Decimal::ZERO);
+        let order = AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 1000, 100)).await.unwrap();
+
+        // Re-pricing counts the change in value of the unfilled part
+        AsyncCoreSDK::modify_price(&sdk, &stock, OrderRef::Result(&order), Decimal::from(150)).await.unwrap();
+        assert_eq!(guard.daily_notional().unwrap(), Decimal::from(150_000));
+        AsyncCoreSDK::modify_price(&sdk, &stock, OrderRef::Result(&order), Decimal::from(120)).await.unwrap();
+        assert_eq!(guard.daily_notional().unwrap(), Decimal::from(120_000));
+        let result = AsyncCoreSDK::modify_price(&sdk, &stock, OrderRef::Result(&order), Decimal::from(255)).await;
+        assert!(rejected_by(result, RiskRule::MaxDailyNotional));
+        assert_eq!(guard.daily_notional().unwrap(), Decimal::from(120_000));
+    }
+
+    #[tokio::test]
+    async fn test_position_limits() {
+        let held = Position {
+            symbol: "2881".to_string(),
+            market_type: MarketType::Stock,
+            action: BSAction::Buy,
+            trading_type: TradingType::Normal,
+            quantity: 2000,
+            avg_cost: Decimal::from(60),
+            last_price: Decimal::from(66),
+            unrealized_pnl: Decimal::from(12000),
+            today_buy_quantity: 0,
+            today_sell_quantity: 0,
+        };
+        let backend = MemoryBackend::new().with_positions("1234567890", vec![held]);
+        let limits = RiskLimits::new().with_position_limit("2881", 3000);
+        let (sdk, stock, _) = login(Arc::new(backend), limits).await.unwrap();
+
+        let result = AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 2000, 66)).await;
+        assert!(rejected_by(result, RiskRule::PositionLimit));
+        AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 1000, 66)).await.unwrap();
+
+        let sell = |quantity| Order::builder("2881", BSAction::Sell, quantity)
+            .with_price(Decimal::from(66))
+            .build()
+            .unwrap();
+        AsyncCoreSDK::place_order(&sdk, &stock, &sell(5000)).await.unwrap();
+        let result = AsyncCoreSDK::place_order(&sdk, &stock, &sell(6000)).await;
+        assert!(rejected_by(result, RiskRule::PositionLimit));
+
+        // Symbols without a limit are not checked
+        AsyncCoreSDK::place_order(&sdk, &stock, &buy("2882", 10000, 40)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restricted_symbols() {
+        let (sdk, stock, _) = memory(RiskLimits::new().with_restricted_symbols(["2330"])).await;
+
+        let result = AsyncCoreSDK::place_order(&sdk, &stock, &buy("2330", 1000, 500)).await;
+        assert!(rejected_by(result, RiskRule::RestrictedSymbol));
+        AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 1000, 66)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_price_collar_against_the_last_trade() {
+        let limits = RiskLimits::new().with_price_collar("0.05".parse().unwrap());
+        let (sdk, stock, _) = memory(limits).await;
+
+        // No last trade to compare with
+        let result = AsyncCoreSDK::place_order(&sdk, &stock, &buy("2330", 1000, 500)).await;
+        assert!(matches!(result, Err(Error::RiskRejected { rule: RiskRule::PriceCollar, ref reason })
+            if reason == "no last trade price for 2330"));
+
+        sdk.risk_guard().record_trade("2330", Decimal::from(500));
+        AsyncCoreSDK::place_order(&sdk, &stock, &buy("2330", 1000, 525)).await.unwrap();
+        AsyncCoreSDK::place_order(&sdk, &stock, &buy("2330", 1000, 475)).await.unwrap();
+        let result = AsyncCoreSDK::place_order(&sdk, &stock, &buy("2330", 1000, 526)).await;
+        assert!(rejected_by(result, RiskRule::PriceCollar));
+
+        // Market orders have no price to collar
+        let market = Order::builder("2330", BSAction::Buy, 1000)
+            .with_order_type(OrderType::Market)
+            .build()
+            .unwrap();
+        AsyncCoreSDK::place_order(&sdk, &stock, &market).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_notional_released_when_placement_fails() {
+        let backend = PaperBackend::new().with_cash("1234567890", Decimal::from(150_000));
+        let limits = RiskLimits::new().with_max_daily_notional(Decimal::from(1_000_000));
+        let (sdk, stock, _) = login(Arc::new(backend), limits).await.unwrap();
+
+        AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 1000, 100)).await.unwrap();
+        assert_eq!(sdk.risk_guard().daily_notional().unwrap(), Decimal::from(100_000));
+
+        // The paper backend has no cash left for a second lot
+        let result = AsyncCoreSDK::place_order(&sdk, &stock, &buy("2881", 1000, 100)).await;
+        assert!(matches!(result, Err(Error::Api(_))));
+        assert_eq!(sdk.risk_guard().daily_notional().unwrap(), Decimal::from(100_000));
+    }
+}
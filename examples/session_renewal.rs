@@ -0,0 +1,229 @@
+/*!
+# 連線逾時自動重新登入範例 (P.O.C)
+
+⚠️ **P.O.C 專案**: 本範例僅供概念驗證和學習使用，不適用於實際交易。
+👨‍💻 **開發者**: Steve Lo (info@sd.idv.tw)
+
+本範例以記憶體後端示範連線逾時後的自動重新登入：券商回傳
+`Error::Unauthenticated` 時，SDK 以上次登入的認證資訊重新登入並重送請求，
+帳戶清單也改為重新登入時券商回傳的內容。
+
+## 功能
+- 模擬券商中斷連線
+- 自動重新登入並重送請求
+- 訂閱連線事件 (`SessionEvent`)
+- 重新登入後更新帳戶清單
+
+## 使用方法
+```bash
+cargo run --example session_renewal --features memory
+cargo test --example session_renewal --features memory
+```
+*/
+
+use std::sync::Arc;
+use r_fubon_neo::backend::MemoryBackend;
+use r_fubon_neo::{AsyncCoreSDK, FubonSDK, LoginCredentials, Result, SessionEvent};
+
+fn credentials() -> LoginCredentials {
+    LoginCredentials {
+        personal_id: "A123456789".to_string(),
+        password: "secret".to_string(),
+        cert_path: "/path/to/cert.p12".to_string(),
+        cert_pass: None,
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔄 連線逾時自動重新登入範例");
+
+    let backend = Arc::new(MemoryBackend::new());
+    let mut sdk = FubonSDK::with_backend(backend.clone());
+    let mut events = sdk.session().subscribe();
+
+    let accounts = AsyncCoreSDK::login(&mut sdk, credentials()).await?;
+    println!("✅ 登入成功，共 {} 個帳戶", accounts.len());
+
+    // 券商中斷連線後的下一個請求會先重新登入
+    backend.expire_session()?;
+    let balance = AsyncCoreSDK::get_account_balance(&sdk, &accounts[0]).await?;
+    println!("💰 購買力: {}", balance.buying_power);
+    println!("🔑 登入次數: {}", backend.login_count()?);
+
+    while let Ok(event) = events.try_recv() {
+        if let SessionEvent::Renewed { expires_at } = event {
+            println!("🔄 已重新登入，到期時間: {:?}", expires_at);
+        }
+    }
+    println!("📋 目前帳戶: {}", sdk.accounts().len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+    use r_fubon_neo::session::{LoginSession, RealtimeToken};
+    use r_fubon_neo::{
+        Account, Backend, Balance, ConditionOrder, Decimal, Error, FillRecord, FutOptConditionOrder,
+        FutOptEquity, FutOptOrder, FutOptPositions, HistoryStatus, MarginQuota, Order, OrderResult,
+        Position, Price, RealizedPnl, TradeEvents, UnrealizedPnl,
+    };
+
+    /// Memory backend whose re-logins return a different account list
+    struct ChangingAccounts {
+        inner: MemoryBackend,
+        after_relogin: Vec<Account>,
+    }
+
+    #[async_trait]
+    impl Backend for ChangingAccounts {
+        fn events(&self) -> TradeEvents {
+            self.inner.events()
+        }
+
+        async fn login(&self, credentials: &LoginCredentials) -> Result<LoginSession> {
+            let mut session = self.inner.login(credentials).await?;
+            if self.inner.login_count()? > 1 {
+                session.accounts = self.after_relogin.clone();
+            }
+            Ok(session)
+        }
+
+        async fn logout(&self) -> Result<()> {
+            self.inner.logout().await
+        }
+
+        async fn exchange_realtime_token(&self) -> Result<RealtimeToken> {
+            self.inner.exchange_realtime_token().await
+        }
+
+        async fn place_order(&self, account: &Account, order: &Order) -> Result<OrderResult> {
+            self.inner.place_order(account, order).await
+        }
+
+        async fn place_condition_order(&self, account: &Account, condition_order: &ConditionOrder) -> Result<OrderResult> {
+            self.inner.place_condition_order(account, condition_order).await
+        }
+
+        async fn place_futopt_order(&self, account: &Account, order: &FutOptOrder) -> Result<OrderResult> {
+            self.inner.place_futopt_order(account, order).await
+        }
+
+        async fn place_futopt_condition_order(&self, account: &Account, condition_order: &FutOptConditionOrder) -> Result<OrderResult> {
+            self.inner.place_futopt_condition_order(account, condition_order).await
+        }
+
+        async fn modify_price(&self, account: &Account, order: &OrderResult, price: Price) -> Result<OrderResult> {
+            self.inner.modify_price(account, order, price).await
+        }
+
+        async fn modify_quantity(&self, account: &Account, order: &OrderResult, quantity: u32) -> Result<OrderResult> {
+            self.inner.modify_quantity(account, order, quantity).await
+        }
+
+        async fn cancel_order(&self, account: &Account, order_id: &str) -> Result<()> {
+            self.inner.cancel_order(account, order_id).await
+        }
+
+        async fn get_order_status(&self, account: &Account, order_id: &str) -> Result<OrderResult> {
+            self.inner.get_order_status(account, order_id).await
+        }
+
+        async fn get_order_results(&self, account: &Account) -> Result<Vec<OrderResult>> {
+            self.inner.get_order_results(account).await
+        }
+
+        async fn get_order_history(
+            &self,
+            account: &Account,
+            from: NaiveDate,
+            to: NaiveDate,
+            status: Option<HistoryStatus>,
+        ) -> Result<Vec<OrderResult>> {
+            self.inner.get_order_history(account, from, to, status).await
+        }
+
+        async fn get_filled_history(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<FillRecord>> {
+            self.inner.get_filled_history(account, from, to).await
+        }
+
+        async fn get_realized_pnl(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<RealizedPnl>> {
+            self.inner.get_realized_pnl(account, from, to).await
+        }
+
+        async fn get_unrealized_pnl(&self, account: &Account) -> Result<Vec<UnrealizedPnl>> {
+            self.inner.get_unrealized_pnl(account).await
+        }
+
+        async fn get_account_balance(&self, account: &Account) -> Result<Balance> {
+            self.inner.get_account_balance(account).await
+        }
+
+        async fn get_futopt_equity(&self, account: &Account) -> Result<FutOptEquity> {
+            self.inner.get_futopt_equity(account).await
+        }
+
+        async fn get_futopt_positions(&self, account: &Account) -> Result<FutOptPositions> {
+            self.inner.get_futopt_positions(account).await
+        }
+
+        async fn get_margin_quota(&self, account: &Account, symbol: &str) -> Result<MarginQuota> {
+            self.inner.get_margin_quota(account, symbol).await
+        }
+
+        async fn get_positions(&self, account: &Account) -> Result<Vec<Position>> {
+            self.inner.get_positions(account).await
+        }
+    }
+
+    /// Stock account alone, with a lower available balance
+    fn after_relogin() -> Vec<Account> {
+        vec![Account {
+            account_id: "1234567890".to_string(),
+            account_name: "Main Trading Account".to_string(),
+            account_type: "stock".to_string(),
+            status: "active".to_string(),
+            currency: "TWD".to_string(),
+            available_balance: Some(Decimal::from(80000)),
+            total_balance: Some(Decimal::from(120000)),
+        }]
+    }
+
+    #[tokio::test]
+    async fn test_relogin_refreshes_accounts() {
+        let backend = Arc::new(ChangingAccounts { inner: MemoryBackend::new(), after_relogin: after_relogin() });
+        let mut sdk = FubonSDK::with_backend(backend.clone());
+        let accounts = AsyncCoreSDK::login(&mut sdk, credentials()).await.unwrap();
+        assert_eq!(sdk.accounts().len(), 2);
+
+        // The broker answers the next request with Unauthenticated once
+        backend.inner.expire_session().unwrap();
+        AsyncCoreSDK::get_positions(&sdk, &accounts[0]).await.unwrap();
+        assert_eq!(backend.inner.login_count().unwrap(), 2);
+
+        let refreshed = sdk.accounts();
+        assert_eq!(refreshed.len(), 1);
+        assert_eq!(refreshed[0].account_id, accounts[0].account_id);
+        assert_eq!(refreshed[0].available_balance, Some(Decimal::from(80000)));
+
+        // The futures account is no longer part of this login
+        let result = AsyncCoreSDK::get_account_balance(&sdk, &accounts[1]).await;
+        assert!(matches!(result, Err(Error::General(ref msg))
+            if msg == "Account 0987654321 is not available for this login"));
+    }
+
+    #[tokio::test]
+    async fn test_logout_clears_accounts() {
+        let mut sdk = FubonSDK::with_backend(Arc::new(MemoryBackend::new()));
+        AsyncCoreSDK::login(&mut sdk, credentials()).await.unwrap();
+        assert_eq!(sdk.accounts().len(), 2);
+
+        AsyncCoreSDK::logout(&mut sdk).await.unwrap();
+        assert!(sdk.accounts().is_empty());
+        assert!(sdk.session().accounts().is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+/*!
+# 升降單位與漲跌幅範例 (P.O.C)
+
+⚠️ **P.O.C 專案**: 本範例僅供概念驗證和學習使用，不適用於實際交易。
+👨‍💻 **開發者**: Steve Lo (info@sd.idv.tw)
+
+本範例展示股票與 ETF 的升降單位 (`TickLadder`)、以參考價計算的
+漲跌停價格 (`PriceLimits`)，以及下單前的價格檢查。
+
+## 功能
+- 股票與 ETF 的升降單位
+- 將價格調整為合法檔位
+- 參考價 ±10% 的漲跌停價格
+- 限價的檔位與漲跌幅檢查
+
+## 使用方法
+```bash
+cargo run --example tick_sizes
+cargo test --example tick_sizes
+```
+*/
+
+use r_fubon_neo::tick::validate_price;
+use r_fubon_neo::{Price, PriceLimits, Result, TickLadder};
+
+fn price(text: &str) -> Price {
+    text.parse().expect("valid price")
+}
+
+fn main() -> Result<()> {
+    println!("📏 升降單位與漲跌幅範例");
+
+    // 1. 升降單位
+    for (symbol, text) in [("2330", "9.99"), ("2330", "49.95"), ("2330", "1000"), ("0050", "50")] {
+        let ladder = TickLadder::for_symbol(symbol);
+        println!("  {} {:>7} -> 升降單位 {} ({:?})", symbol, text, ladder.tick_size(price(text)), ladder);
+    }
+
+    // 2. 調整為合法價格
+    let ladder = TickLadder::Stock;
+    println!("\n🔧 10.03 -> 向下 {} / 向上 {} / 最近 {}",
+        ladder.round_down(price("10.03")), ladder.round_up(price("10.03")), ladder.round(price("10.03")));
+
+    // 3. 漲跌停價格
+    let limits = PriceLimits::new(price("66.5"), TickLadder::Stock);
+    println!("\n📊 參考價 {} -> 跌停 {} / 漲停 {}", limits.reference, limits.limit_down, limits.limit_up);
+
+    // 4. 價格檢查
+    for text in ["73.1", "73.2", "66.55"] {
+        match validate_price("2881", price(text), Some(limits.reference)) {
+            Ok(()) => println!("  ✅ {}", text),
+            Err(e) => println!("  ❌ {}", e),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use r_fubon_neo::{Decimal, Error};
+
+    #[test]
+    fn test_stock_ticks_at_band_edges() {
+        let ladder = TickLadder::Stock;
+        let cases = [
+            ("9.99", "0.01"),
+            ("10", "0.05"),
+            ("49.95", "0.05"),
+            ("50", "0.1"),
+            ("99.9", "0.1"),
+            ("100", "0.5"),
+            ("499.5", "0.5"),
+            ("500", "1"),
+            ("995", "1"),
+            ("1000", "5"),
+        ];
+
+        for (at, tick) in cases {
+            assert_eq!(ladder.tick_size(price(at)), price(tick), "tick at {}", at);
+            assert!(ladder.is_valid(price(at)), "{} is on the ladder", at);
+        }
+        assert!(!ladder.is_valid(price("10.01")));
+        assert!(!ladder.is_valid(price("1001")));
+        assert!(!ladder.is_valid(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_etf_ladder() {
+        assert_eq!(TickLadder::for_symbol("0050"), TickLadder::Etf);
+        assert_eq!(TickLadder::for_symbol("00878"), TickLadder::Etf);
+        assert_eq!(TickLadder::for_symbol("2330"), TickLadder::Stock);
+        assert_eq!(TickLadder::for_symbol("1101"), TickLadder::Stock);
+
+        let ladder = TickLadder::Etf;
+        assert_eq!(ladder.tick_size(price("49.99")), price("0.01"));
+        assert_eq!(ladder.tick_size(price("50")), price("0.05"));
+        assert_eq!(ladder.tick_size(price("1200")), price("0.05"));
+        assert!(ladder.is_valid(price("10.03")));
+        assert!(!ladder.is_valid(price("50.01")));
+    }
+
+    #[test]
+    fn test_rounding_across_band_boundaries() {
+        let stock = TickLadder::Stock;
+        assert_eq!(stock.round_up(price("9.999")), price("10"));
+        assert_eq!(stock.round_down(price("9.999")), price("9.99"));
+        assert_eq!(stock.round_up(price("10.01")), price("10.05"));
+        assert_eq!(stock.round_down(price("10.01")), price("10"));
+        assert_eq!(stock.round_up(price("49.96")), price("50"));
+        assert_eq!(stock.round_up(price("50.01")), price("50.1"));
+        assert_eq!(stock.round_up(price("999.5")), price("1000"));
+        assert_eq!(stock.round_down(price("1004")), price("1000"));
+        assert_eq!(stock.round(price("10.025")), price("10.05"));
+        assert_eq!(stock.round(price("10.02")), price("10"));
+
+        let etf = TickLadder::Etf;
+        assert_eq!(etf.round_up(price("49.999")), price("50"));
+        assert_eq!(etf.round_up(price("50.01")), price("50.05"));
+        assert_eq!(etf.round_down(price("50.04")), price("50"));
+    }
+
+    #[test]
+    fn test_price_limits_round_inwards() {
+        let limits = PriceLimits::new(price("500"), TickLadder::Stock);
+        assert_eq!((limits.limit_down, limits.limit_up), (price("450"), price("550")));
+
+        // 59.85 and 73.15 are not on the 0.1 ladder
+        let limits = PriceLimits::new(price("66.5"), TickLadder::Stock);
+        assert_eq!((limits.limit_down, limits.limit_up), (price("59.9"), price("73.1")));
+        assert!(limits.contains(price("73.1")));
+        assert!(!limits.contains(price("73.2")));
+        assert!(!limits.contains(price("59.8")));
+
+        let limits = PriceLimits::new(price("21.7"), TickLadder::Stock);
+        assert_eq!((limits.limit_down, limits.limit_up), (price("19.55"), price("23.85")));
+    }
+
+    #[test]
+    fn test_validate_price() {
+        validate_price("2330", price("500"), None).unwrap();
+        validate_price("2330", price("10000"), None).unwrap();
+        validate_price("0050", price("50.05"), None).unwrap();
+
+        let result = validate_price("2330", price("500.5"), None);
+        assert!(matches!(result, Err(Error::InvalidPrice { ref reason, .. })
+            if reason == "not a multiple of the 1 tick, nearest valid price is 501"));
+
+        // With a reference price the daily limits apply as well
+        validate_price("2330", price("550"), Some(price("500"))).unwrap();
+        validate_price("2330", price("450"), Some(price("500"))).unwrap();
+        let result = validate_price("2330", price("551"), Some(price("500")));
+        assert!(matches!(result, Err(Error::InvalidPrice { ref reason, .. })
+            if reason == "outside the daily limits 450 to 550"));
+        let result = validate_price("2330", price("449"), Some(price("500")));
+        assert!(matches!(result, Err(Error::InvalidPrice { .. })));
+    }
+}
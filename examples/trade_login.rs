@@ -0,0 +1,210 @@
+/*!
+# 本機交易端點登入範例 (P.O.C)
+
+⚠️ **P.O.C 專案**: 本範例僅供概念驗證和學習使用，不適用於實際交易。
+👨‍💻 **開發者**: Steve Lo (info@sd.idv.tw)
+
+本範例啟動一個本機的交易 API 替身伺服器，透過 `TradeConfig::with_base_url`
+將 `HttpBackend` 指向它，再以 `FubonSDK::with_backend` 使用 PKCS#12 憑證
+完成登入流程。CI 可以用同樣的方式在
+不連線券商的情況下測試登入。
+
+## 使用方法
+```bash
+cargo run --example trade_login
+```
+*/
+
+use std::sync::Arc;
+use r_fubon_neo::backend::HttpBackend;
+use r_fubon_neo::{AsyncCoreSDK, Error, FubonSDK, LoginCredentials, Result, TradeConfig};
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// 測試用 PKCS#12 憑證 (自簽，僅供本機替身伺服器使用)
+const TEST_CERT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/fixtures/test_client.p12");
+const TEST_CERT_PASS: &str = "test1234";
+
+/// 替身伺服器收到的請求
+#[derive(Debug)]
+struct CapturedRequest {
+    method: String,
+    path: String,
+    body: Value,
+}
+
+/// 啟動只回應一次請求的本機 HTTP 伺服器，回傳 base URL 與收到的請求
+async fn spawn_stand_in(response: Value) -> Result<(String, JoinHandle<Result<CapturedRequest>>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let base_url = format!("http://{}", listener.local_addr()?);
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await?;
+
+        // 讀取標頭，再依 Content-Length 讀取主體
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Err(Error::connection("Connection closed before request headers"));
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+            if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+        let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+        let method = request_line.next().unwrap_or_default().to_string();
+        let path = request_line.next().unwrap_or_default().to_string();
+        let content_length = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+
+        while buffer.len() < header_end + content_length {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+
+        let body = serde_json::from_slice(&buffer[header_end..header_end + content_length])?;
+
+        let payload = response.to_string();
+        let reply = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            payload.len(),
+            payload
+        );
+        stream.write_all(reply.as_bytes()).await?;
+        stream.shutdown().await?;
+
+        Ok(CapturedRequest { method, path, body })
+    });
+
+    Ok((base_url, handle))
+}
+
+/// 替身伺服器的成功登入回應
+fn login_success() -> Value {
+    json!({
+        "is_success": true,
+        "data": {
+            "token": "stand-in-token",
+            "expires_at": "2030-01-01T08:00:00Z",
+            "accounts": [{
+                "account_id": "1234567",
+                "account_name": "測試帳戶",
+                "account_type": "stock",
+                "status": "active",
+                "currency": "TWD",
+                "available_balance": "100000",
+                "total_balance": "120000.5"
+            }]
+        }
+    })
+}
+
+fn test_credentials() -> LoginCredentials {
+    LoginCredentials {
+        personal_id: "A123456789".to_string(),
+        password: "secret".to_string(),
+        cert_path: TEST_CERT_PATH.to_string(),
+        cert_pass: Some(TEST_CERT_PASS.to_string()),
+    }
+}
+
+/// 連線至替身伺服器的 SDK
+fn stand_in_sdk(base_url: String) -> FubonSDK {
+    let config = TradeConfig::new().with_base_url(base_url);
+    FubonSDK::with_backend(Arc::new(HttpBackend::new(config)))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🚀 啟動本機交易 API 替身伺服器...");
+    let (base_url, server) = spawn_stand_in(login_success()).await?;
+    println!("📡 替身伺服器: {}", base_url);
+
+    let mut sdk = stand_in_sdk(base_url);
+
+    println!("🔐 執行登入...");
+    let accounts = AsyncCoreSDK::login(&mut sdk, test_credentials()).await?;
+    let request = server.await.map_err(|e| Error::general(e.to_string()))??;
+
+    println!("✅ 伺服器收到: {} {} (personal_id: {})", request.method, request.path, request.body["personal_id"]);
+    println!("✅ 登入成功! 找到 {} 個帳戶:", accounts.len());
+    for account in &accounts {
+        println!("   - {} ({})", account.account_name, account.account_id);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use r_fubon_neo::Decimal;
+
+    #[tokio::test]
+    async fn test_login_against_stand_in_server() {
+        let (base_url, server) = spawn_stand_in(login_success()).await.unwrap();
+        let mut sdk = stand_in_sdk(base_url);
+
+        let accounts = AsyncCoreSDK::login(&mut sdk, test_credentials()).await.unwrap();
+        let request = server.await.unwrap().unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/v1/login");
+        assert_eq!(request.body["personal_id"], "A123456789");
+        assert_eq!(request.body["password"], "secret");
+        assert_eq!(request.body["sdk_version"], r_fubon_neo::VERSION);
+
+        assert!(sdk.is_logged_in());
+        assert_eq!(sdk.session().expires_at().unwrap().to_rfc3339(), "2030-01-01T08:00:00+00:00");
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].account_id, "1234567");
+        assert!(accounts[0].is_stock());
+        assert_eq!(accounts[0].total_balance, Some("120000.5".parse::<Decimal>().unwrap()));
+        assert_eq!(sdk.accounts()[0].account_id, "1234567");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_login_is_authentication_error() {
+        let (base_url, server) = spawn_stand_in(json!({
+            "is_success": false,
+            "message": "密碼錯誤",
+            "data": null
+        })).await.unwrap();
+        let mut sdk = stand_in_sdk(base_url);
+
+        let result = AsyncCoreSDK::login(&mut sdk, test_credentials()).await;
+        server.await.unwrap().unwrap();
+
+        assert!(matches!(result, Err(Error::Authentication(ref msg)) if msg == "密碼錯誤"));
+        assert!(!sdk.is_logged_in());
+        assert!(sdk.accounts().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wrong_certificate_password() {
+        let credentials = LoginCredentials {
+            cert_pass: Some("wrong".to_string()),
+            ..test_credentials()
+        };
+
+        // The certificate is rejected before any request is sent
+        let mut sdk = stand_in_sdk("http://127.0.0.1:9".to_string());
+        let result = AsyncCoreSDK::login(&mut sdk, credentials).await;
+        assert!(matches!(result, Err(Error::Certificate(_))));
+        assert!(!sdk.is_logged_in());
+    }
+}
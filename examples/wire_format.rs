@@ -0,0 +1,190 @@
+/*!
+# 交易列舉的傳輸格式範例 (P.O.C)
+
+⚠️ **P.O.C 專案**: 本範例僅供概念驗證和學習使用，不適用於實際交易。
+👨‍💻 **開發者**: Steve Lo (info@sd.idv.tw)
+
+本範例展示交易列舉在 JSON 中使用與 Python `fubon_neo` 相同的名稱
+(例如 ROD/IOC/FOK、B/S、Cash/Margin/Short/DayTrade)。
+
+⚠️ 只有列舉值相容：委託單的欄位名稱沿用本套件的 `Order`
+(`action`、`trading_type`、`session`、`order_type` 為限價/市價)，
+與 Python `Order` 的 `buy_sell`、`market_type`、`price_type`、
+`order_type` (現股/融資/融券) 不同，兩者的委託 JSON 不能直接互換。
+
+## 功能
+- 以傳輸名稱序列化委託單
+- 解析使用 `fubon_neo` 列舉值的委託 JSON
+- 使用 `Display`/`FromStr` 轉換列舉
+
+## 使用方法
+```bash
+cargo run --example wire_format
+```
+*/
+
+use r_fubon_neo::{BSAction, Decimal, Order, Result, TimeInForce, TradingType};
+
+/// Order in this crate's field layout, with the `fubon_neo` enum values
+const WIRE_ORDER: &str = r#"{
+    "symbol": "2881",
+    "quantity": 2000,
+    "price": "66.5",
+    "stop_price": null,
+    "order_type": "Limit",
+    "time_in_force": "IOC",
+    "action": "S",
+    "trading_type": "DayTrade",
+    "session": "Common"
+}"#;
+
+fn main() -> Result<()> {
+    println!("🔁 交易列舉傳輸格式範例");
+
+    // 1. Rust 委託單序列化
+    let order = Order::builder("2330", BSAction::Buy, 1000)
+        .with_price(Decimal::from(500))
+        .with_trading_type(TradingType::Margin)
+        .build()?;
+    println!("📤 Rust 送出: {}", serde_json::to_string(&order)?);
+
+    // 2. 解析使用 fubon_neo 列舉值的委託單
+    let received: Order = serde_json::from_str(WIRE_ORDER)?;
+    println!("📥 收到: {} {} {} 股 @ {} ({}, {})",
+        received.action, received.symbol, received.quantity,
+        received.price.unwrap_or_default(), received.time_in_force, received.trading_type);
+
+    // 3. Display / FromStr
+    let time_in_force: TimeInForce = "FOK".parse()?;
+    println!("🔤 \"FOK\" -> {:?} -> \"{}\"", time_in_force, time_in_force);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Debug;
+    use std::str::FromStr;
+    use serde::{de::DeserializeOwned, Serialize};
+    use r_fubon_neo::*;
+
+    /// Check a value against its wire name through serde, `Display` and `FromStr`
+    fn assert_wire<T>(value: T, wire: &str)
+    where
+        T: Serialize + DeserializeOwned + FromStr<Err = Error> + std::fmt::Display + PartialEq + Debug + Copy,
+    {
+        assert_eq!(serde_json::to_value(value).unwrap(), serde_json::json!(wire));
+        assert_eq!(serde_json::from_value::<T>(serde_json::json!(wire)).unwrap(), value);
+        assert_eq!(value.to_string(), wire);
+        assert_eq!(wire.parse::<T>().unwrap(), value);
+    }
+
+    #[test]
+    fn test_python_sdk_names() {
+        assert_wire(TimeInForce::Day, "ROD");
+        assert_wire(TimeInForce::Ioc, "IOC");
+        assert_wire(TimeInForce::Fok, "FOK");
+        assert_wire(BSAction::Buy, "B");
+        assert_wire(BSAction::Sell, "S");
+        assert_wire(TradingType::Normal, "Cash");
+        assert_wire(TradingType::Margin, "Margin");
+        assert_wire(TradingType::Short, "Short");
+        assert_wire(TradingType::DayTrade, "DayTrade");
+        assert_wire(MarketSession::Regular, "Common");
+        assert_wire(MarketSession::AfterHoursFixedPrice, "Fixing");
+        assert_wire(MarketSession::AfterHoursOddLot, "Odd");
+        assert_wire(MarketSession::IntradayOddLot, "IntradayOdd");
+        assert_wire(Direction::Sell, "S");
+        assert_wire(TriggerContent::Price, "MatchedPrice");
+        assert_wire(TriggerContent::Volume, "TotalQuantity");
+        assert_wire(Operator::GreaterEqual, "GreaterThanOrEqual");
+        assert_wire(Operator::LessEqual, "LessThanOrEqual");
+        assert_wire(ConditionOrderType::Oco, "OCO");
+    }
+
+    #[test]
+    fn test_every_enum_round_trips() {
+        assert_wire(TimeInForce::Gtc, "GTC");
+        assert_wire(OrderType::StopLimit, "StopLimit");
+        assert_wire(PriceType::MarketOnClose, "MarketOnClose");
+        assert_wire(MarketType::Option, "Option");
+        assert_wire(FutOptMarketType::Future, "Future");
+        assert_wire(FutOptOrderType::Stop, "Stop");
+        assert_wire(FutOptPriceType::Market, "Market");
+        assert_wire(CallPut::Put, "Put");
+        assert_wire(ConditionPriceType::Limit, "Limit");
+        assert_wire(ConditionMarketType::Future, "Future");
+        assert_wire(StopSign::Down, "Down");
+        assert_wire(ConditionStatus::Triggered, "Triggered");
+        assert_wire(HistoryStatus::PartiallyFilled, "PartiallyFilled");
+        assert_wire(OrderStatus::Working, "Working");
+        assert_wire(CombinationType::Straddle, "Straddle");
+    }
+
+    #[test]
+    fn test_rust_variant_names_are_rejected() {
+        assert!("Day".parse::<TimeInForce>().is_err());
+        assert!("Buy".parse::<BSAction>().is_err());
+        assert!("Normal".parse::<TradingType>().is_err());
+        assert!(serde_json::from_str::<MarketSession>("\"IntradayOddLot\"").is_err());
+
+        // Listed aliases are still accepted
+        assert_eq!("Stock".parse::<TradingType>().unwrap(), TradingType::Normal);
+    }
+
+    #[test]
+    fn test_unknown_names() {
+        assert!("GTD".parse::<TimeInForce>().is_err());
+        assert!(serde_json::from_str::<BSAction>("\"X\"").is_err());
+        assert_eq!("Butterfly".parse::<CombinationType>().unwrap(), CombinationType::Other);
+        assert_eq!(serde_json::from_str::<OrderStatus>("\"PendingCancel\"").unwrap(), OrderStatus::Unknown);
+        assert!(!OrderStatus::Unknown.is_active());
+    }
+
+    #[test]
+    fn test_wire_order_round_trip() {
+        let order: Order = serde_json::from_str(WIRE_ORDER).unwrap();
+        assert_eq!(order.action, BSAction::Sell);
+        assert_eq!(order.time_in_force, TimeInForce::Ioc);
+        assert_eq!(order.trading_type, TradingType::DayTrade);
+        assert_eq!(order.session, MarketSession::Regular);
+        assert_eq!(order.price, Some(Decimal::new(665, 1)));
+
+        let sent = serde_json::to_value(&order).unwrap();
+        assert_eq!(sent["action"], "S");
+        assert_eq!(sent["time_in_force"], "IOC");
+        assert_eq!(sent["trading_type"], "DayTrade");
+        assert_eq!(sent["session"], "Common");
+        assert_eq!(sent["price"], "66.5");
+        assert_eq!(serde_json::from_value::<Order>(sent).unwrap().price, order.price);
+    }
+
+    #[test]
+    fn test_python_field_names_are_not_mapped() {
+        // Field names of the Python SDK's Order; only the enum values are shared
+        let python_layout = serde_json::json!({
+            "buy_sell": "S",
+            "symbol": "2881",
+            "price": "66.5",
+            "quantity": 2000,
+            "market_type": "Common",
+            "price_type": "Limit",
+            "time_in_force": "IOC",
+            "order_type": "DayTrade"
+        });
+        assert!(serde_json::from_value::<Order>(python_layout).is_err());
+    }
+
+    #[test]
+    fn test_decimals_round_trip_exactly() {
+        let amount: Decimal = "12345678901234567.123456789".parse().unwrap();
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(json, "\"12345678901234567.123456789\"");
+        assert_eq!(serde_json::from_str::<Decimal>(&json).unwrap(), amount);
+
+        // Numbers are still accepted
+        assert_eq!(serde_json::from_str::<Decimal>("66.5").unwrap(), Decimal::new(665, 1));
+        assert_eq!(serde_json::from_str::<Decimal>("2000").unwrap(), Decimal::from(2000));
+    }
+}
@@ -0,0 +1,243 @@
+use std::sync::{Mutex, RwLock};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio_util::sync::CancellationToken;
+
+use crate::{Result, Error};
+use crate::backend::{Backend, OrderAck};
+use crate::decimal::Price;
+use crate::pnl::{RealizedPnl, UnrealizedPnl};
+use crate::events::TradeEvents;
+use crate::session::{LoginSession, RealtimeToken};
+use crate::trade::events::spawn_event_listener;
+use crate::trade::{TradeClient, TradeConfig};
+use crate::types::*;
+
+#[derive(Serialize)]
+struct HistoryQuery {
+    from: NaiveDate,
+    to: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<HistoryStatus>,
+}
+
+/// Path of an account scoped endpoint
+fn account_path(account: &Account, path: &str) -> String {
+    format!("/api/v1/accounts/{}{}", account.account_id, path)
+}
+
+/// Backend talking to the trading API over HTTPS.
+///
+/// After login, trading events are received from the API's event WebSocket.
+pub struct HttpBackend {
+    config: TradeConfig,
+    client: RwLock<Option<TradeClient>>,
+    events: TradeEvents,
+    listener: Mutex<Option<CancellationToken>>,
+}
+
+impl HttpBackend {
+    pub fn new(config: TradeConfig) -> Self {
+        Self {
+            config,
+            client: RwLock::new(None),
+            events: TradeEvents::new(),
+            listener: Mutex::new(None),
+        }
+    }
+
+    /// Get trading API configuration
+    pub fn config(&self) -> &TradeConfig {
+        &self.config
+    }
+
+    /// Get a handle to the logged in client
+    fn client(&self) -> Result<TradeClient> {
+        self.client
+            .read()
+            .map_err(|_| Error::general("Trade client lock poisoned"))?
+            .clone()
+            .ok_or(Error::Unauthenticated)
+    }
+
+    /// (Re)start the trading event listener for a new session
+    fn start_listener(&self, token: &str) -> Result<()> {
+        let listener = spawn_event_listener(self.config.events_url(), token.to_string(), self.events.clone())?;
+        self.replace_listener(Some(listener))
+    }
+
+    fn replace_listener(&self, listener: Option<CancellationToken>) -> Result<()> {
+        let previous = std::mem::replace(
+            &mut *self.listener
+                .lock()
+                .map_err(|_| Error::general("Event listener lock poisoned"))?,
+            listener,
+        );
+        if let Some(previous) = previous {
+            previous.cancel();
+        }
+        Ok(())
+    }
+}
+
+impl Drop for HttpBackend {
+    fn drop(&mut self) {
+        if let Ok(mut listener) = self.listener.lock() {
+            if let Some(listener) = listener.take() {
+                listener.cancel();
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for HttpBackend {
+    fn events(&self) -> TradeEvents {
+        self.events.clone()
+    }
+
+    async fn login(&self, credentials: &LoginCredentials) -> Result<LoginSession> {
+        // Load the certificate as TLS client identity and authenticate over HTTPS
+        let mut client = TradeClient::new(self.config.clone(), credentials)?;
+        let response = client.login(credentials).await?;
+        self.start_listener(&response.token)?;
+
+        *self.client
+            .write()
+            .map_err(|_| Error::general("Trade client lock poisoned"))? = Some(client);
+
+        Ok(LoginSession {
+            accounts: response.accounts,
+            expires_at: response.expires_at,
+        })
+    }
+
+    async fn logout(&self) -> Result<()> {
+        let client = self.client
+            .write()
+            .map_err(|_| Error::general("Trade client lock poisoned"))?
+            .take();
+        self.replace_listener(None)?;
+
+        // A session the server already dropped counts as logged out
+        match client {
+            Some(client) => match client.post::<Value, _>("/api/v1/logout", &json!({})).await {
+                Ok(_) | Err(Error::Unauthenticated) => Ok(()),
+                Err(e) => Err(e),
+            },
+            None => Ok(()),
+        }
+    }
+
+    async fn exchange_realtime_token(&self) -> Result<RealtimeToken> {
+        self.client()?
+            .post("/api/v1/realtime/token", &json!({}))
+            .await
+    }
+
+    async fn place_order(&self, account: &Account, order: &Order) -> Result<OrderResult> {
+        let ack: OrderAck = self.client()?
+            .post(&account_path(account, "/stock/orders"), order)
+            .await?;
+        Ok(ack.into_result(account, OrderRequest::Stock(order.clone())))
+    }
+
+    async fn place_condition_order(&self, account: &Account, condition_order: &ConditionOrder) -> Result<OrderResult> {
+        let ack: OrderAck = self.client()?
+            .post(&account_path(account, "/stock/condition-orders"), condition_order)
+            .await?;
+        Ok(ack.into_result(account, OrderRequest::Condition(condition_order.clone())))
+    }
+
+    async fn place_futopt_order(&self, account: &Account, order: &FutOptOrder) -> Result<OrderResult> {
+        let ack: OrderAck = self.client()?
+            .post(&account_path(account, "/futopt/orders"), order)
+            .await?;
+        Ok(ack.into_result(account, OrderRequest::FutOpt(order.clone())))
+    }
+
+    async fn place_futopt_condition_order(&self, account: &Account, condition_order: &FutOptConditionOrder) -> Result<OrderResult> {
+        let ack: OrderAck = self.client()?
+            .post(&account_path(account, "/futopt/condition-orders"), condition_order)
+            .await?;
+        Ok(ack.into_result(account, OrderRequest::FutOptCondition(condition_order.clone())))
+    }
+
+    async fn modify_price(&self, account: &Account, order: &OrderResult, price: Price) -> Result<OrderResult> {
+        let path = account_path(account, &format!("/orders/{}/modify-price", order.order_no));
+        self.client()?.post(&path, &json!({ "price": price })).await
+    }
+
+    async fn modify_quantity(&self, account: &Account, order: &OrderResult, quantity: u32) -> Result<OrderResult> {
+        let path = account_path(account, &format!("/orders/{}/modify-quantity", order.order_no));
+        self.client()?.post(&path, &json!({ "quantity": quantity })).await
+    }
+
+    async fn cancel_order(&self, account: &Account, order_id: &str) -> Result<()> {
+        let path = account_path(account, &format!("/orders/{}/cancel", order_id));
+        let _: OrderAck = self.client()?.post(&path, &json!({})).await?;
+        Ok(())
+    }
+
+    async fn get_order_status(&self, account: &Account, order_id: &str) -> Result<OrderResult> {
+        let path = account_path(account, &format!("/orders/{}", order_id));
+        self.client()?.get(&path, &()).await
+    }
+
+    async fn get_order_results(&self, account: &Account) -> Result<Vec<OrderResult>> {
+        self.client()?.get(&account_path(account, "/orders"), &()).await
+    }
+
+    async fn get_order_history(
+        &self,
+        account: &Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        status: Option<HistoryStatus>,
+    ) -> Result<Vec<OrderResult>> {
+        let query = HistoryQuery { from, to, status };
+        self.client()?.get(&account_path(account, "/orders/history"), &query).await
+    }
+
+    async fn get_filled_history(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<FillRecord>> {
+        let query = HistoryQuery { from, to, status: None };
+        self.client()?.get(&account_path(account, "/fills"), &query).await
+    }
+
+    async fn get_realized_pnl(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<RealizedPnl>> {
+        let query = HistoryQuery { from, to, status: None };
+        self.client()?.get(&account_path(account, "/pnl/realized"), &query).await
+    }
+
+    async fn get_unrealized_pnl(&self, account: &Account) -> Result<Vec<UnrealizedPnl>> {
+        self.client()?.get(&account_path(account, "/pnl/unrealized"), &()).await
+    }
+
+    async fn get_account_balance(&self, account: &Account) -> Result<Balance> {
+        let mut balance: Balance = self.client()?.get(&account_path(account, "/balance"), &()).await?;
+        balance.account_id = account.account_id.clone();
+        Ok(balance)
+    }
+
+    async fn get_futopt_equity(&self, account: &Account) -> Result<FutOptEquity> {
+        let mut equity: FutOptEquity = self.client()?.get(&account_path(account, "/futopt/equity"), &()).await?;
+        equity.account_id = account.account_id.clone();
+        Ok(equity)
+    }
+
+    async fn get_futopt_positions(&self, account: &Account) -> Result<FutOptPositions> {
+        self.client()?.get(&account_path(account, "/futopt/positions"), &()).await
+    }
+
+    async fn get_margin_quota(&self, account: &Account, symbol: &str) -> Result<MarginQuota> {
+        self.client()?
+            .get(&account_path(account, &format!("/margin-quota/{}", symbol)), &())
+            .await
+    }
+
+    async fn get_positions(&self, account: &Account) -> Result<Vec<Position>> {
+        self.client()?.get(&account_path(account, "/positions"), &()).await
+    }
+}
\ No newline at end of file
@@ -0,0 +1,751 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+
+use crate::{Result, Error};
+use crate::backend::Backend;
+use crate::decimal::{Amount, Decimal, Price};
+use crate::pnl::{RealizedPnl, TradeCharges, UnrealizedPnl};
+use crate::events::{FillEvent, OrderEventKind, TradeEvent, TradeEvents};
+use crate::session::{LoginSession, RealtimeToken};
+use crate::types::*;
+
+#[derive(Debug, Default)]
+struct State {
+    logged_in: bool,
+    expires_at: Option<DateTime<Utc>>,
+    logins: u32,
+    realtime_tokens: u32,
+    next_order_id: u64,
+    orders: HashMap<String, OrderResult>,
+    fills: Vec<FillRecord>,
+}
+
+/// Open quantity of one symbol while replaying fills
+#[derive(Debug, Default)]
+struct Inventory {
+    /// Signed quantity, positive when long
+    quantity: i64,
+    avg_cost: Price,
+    /// Charges of the opening trades not yet released by a close
+    charges: TradeCharges,
+}
+
+/// Trading day `days` after `date`, skipping weekends but not holidays
+pub(crate) fn add_trading_days(mut date: NaiveDate, days: u32) -> NaiveDate {
+    for _ in 0..days {
+        date = date.succ_opt().expect("date in range");
+        while matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            date = date.succ_opt().expect("date in range");
+        }
+    }
+    date
+}
+
+/// In-memory backend for tests and demos.
+///
+/// Accepts any credentials, keeps submitted orders in memory and answers
+/// queries from the configured accounts and positions. Realized profit and
+/// loss replays the account's fills at average cost, starting flat; futures
+/// and options are valued in price points and carry no charges. An account's buying
+/// power is its `available_balance` less today's purchases, and its bank
+/// balance defaults to its `total_balance`. Simulated stock fills settle two
+/// trading days later, without fees or taxes. Order changes and fills are
+/// published as trading events.
+pub struct MemoryBackend {
+    accounts: Vec<Account>,
+    positions: HashMap<String, Vec<Position>>,
+    combined_positions: HashMap<String, Vec<CombinedPosition>>,
+    /// Initial and maintenance margin of futures/options accounts
+    futopt_margins: HashMap<String, (Amount, Amount)>,
+    bank_balances: HashMap<String, Amount>,
+    margin_quotas: HashMap<(String, String), MarginQuota>,
+    state: Mutex<State>,
+    events: TradeEvents,
+    session_ttl: Option<Duration>,
+    realtime_token_ttl: Option<Duration>,
+    latency: Option<std::time::Duration>,
+}
+
+impl MemoryBackend {
+    /// Create a backend with a demo stock and futures account
+    pub fn new() -> Self {
+        let accounts = vec![
+            Account {
+                account_id: "1234567890".to_string(),
+                account_name: "Main Trading Account".to_string(),
+                account_type: "stock".to_string(),
+                status: "active".to_string(),
+                currency: "TWD".to_string(),
+                available_balance: Some(Decimal::from(100000)),
+                total_balance: Some(Decimal::from(120000)),
+            },
+            Account {
+                account_id: "0987654321".to_string(),
+                account_name: "Futures Account".to_string(),
+                account_type: "future".to_string(),
+                status: "active".to_string(),
+                currency: "TWD".to_string(),
+                available_balance: Some(Decimal::from(50000)),
+                total_balance: Some(Decimal::from(60000)),
+            },
+        ];
+
+        Self {
+            accounts,
+            positions: HashMap::new(),
+            combined_positions: HashMap::new(),
+            futopt_margins: HashMap::new(),
+            bank_balances: HashMap::new(),
+            margin_quotas: HashMap::new(),
+            state: Mutex::new(State::default()),
+            events: TradeEvents::new(),
+            session_ttl: None,
+            realtime_token_ttl: None,
+            latency: None,
+        }
+    }
+
+    pub fn with_accounts(mut self, accounts: Vec<Account>) -> Self {
+        self.accounts = accounts;
+        self
+    }
+
+    /// Set the available balance of an account
+    pub fn with_balance(mut self, account_id: &str, balance: Amount) -> Self {
+        if let Some(account) = self.accounts.iter_mut().find(|a| a.account_id == account_id) {
+            account.available_balance = Some(balance);
+        }
+        self
+    }
+
+    /// Set the combined (spread) positions held in a futures/options account
+    pub fn with_combined_positions(mut self, account_id: &str, positions: Vec<CombinedPosition>) -> Self {
+        self.combined_positions.insert(account_id.to_string(), positions);
+        self
+    }
+
+    /// Set the initial and maintenance margin of a futures/options account.
+    ///
+    /// Equity is the account's `total_balance` plus the unrealized profit and
+    /// loss of its positions; margins are zero unless set here.
+    pub fn with_futopt_margin(mut self, account_id: &str, initial_margin: Amount, maintenance_margin: Amount) -> Self {
+        self.futopt_margins.insert(account_id.to_string(), (initial_margin, maintenance_margin));
+        self
+    }
+
+    /// Set the balance of the bank account linked to an account
+    pub fn with_bank_balance(mut self, account_id: &str, balance: Amount) -> Self {
+        self.bank_balances.insert(account_id.to_string(), balance);
+        self
+    }
+
+    /// Set the margin and short quota of a symbol in an account.
+    ///
+    /// Quotas are not drawn down by orders. Symbols without a quota can
+    /// neither be bought on margin nor sold short.
+    pub fn with_margin_quota(mut self, account_id: &str, quota: MarginQuota) -> Self {
+        self.margin_quotas.insert((account_id.to_string(), quota.symbol.clone()), quota);
+        self
+    }
+
+    /// Set the positions held in an account
+    pub fn with_positions(mut self, account_id: &str, positions: Vec<Position>) -> Self {
+        self.positions.insert(account_id.to_string(), positions);
+        self
+    }
+
+    /// Let sessions expire `ttl` after login
+    pub fn with_session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = Some(ttl);
+        self
+    }
+
+    /// Let realtime market data tokens expire `ttl` after they are issued
+    pub fn with_realtime_token_ttl(mut self, ttl: Duration) -> Self {
+        self.realtime_token_ttl = Some(ttl);
+        self
+    }
+
+    /// Delay order submissions and cancels by `latency`, as a broker round trip would
+    pub fn with_latency(mut self, latency: std::time::Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Invalidate the current session, as if the broker had dropped it
+    pub fn expire_session(&self) -> Result<()> {
+        self.state()?.logged_in = false;
+        Ok(())
+    }
+
+    /// Number of successful logins so far
+    pub fn login_count(&self) -> Result<u32> {
+        Ok(self.state()?.logins)
+    }
+
+    /// Number of realtime tokens issued so far
+    pub fn realtime_token_count(&self) -> Result<u32> {
+        Ok(self.state()?.realtime_tokens)
+    }
+
+    /// Set the status of a stored order, e.g. to simulate a rejection
+    pub fn set_order_status(&self, order_id: &str, status: OrderStatus) -> Result<()> {
+        let order = {
+            let mut state = self.state()?;
+            let order = Self::order_mut(&mut state, order_id)?;
+            order.status = status;
+            order.updated_at = Utc::now();
+            order.clone()
+        };
+
+        let kind = match status {
+            OrderStatus::Rejected => OrderEventKind::Rejected,
+            OrderStatus::Cancelled => OrderEventKind::Cancelled,
+            _ => OrderEventKind::StatusChanged,
+        };
+        self.events.emit(TradeEvent::order(kind, order));
+        Ok(())
+    }
+
+    /// Simulate a fill of `quantity` at `price` for a working order
+    pub fn fill_order(&self, order_id: &str, quantity: u32, price: Price) -> Result<OrderResult> {
+        let (order, fill) = self.record_fill(order_id, quantity, price)?;
+        self.events.emit(TradeEvent::Filled(FillEvent { fill, order: order.clone() }));
+        Ok(order)
+    }
+
+    /// Record a fill without publishing it, for callers that publish it once
+    /// their own state is updated
+    pub(crate) fn record_fill(&self, order_id: &str, quantity: u32, price: Price) -> Result<(OrderResult, FillRecord)> {
+        let mut state = self.state()?;
+        let order = Self::order_mut(&mut state, order_id)?;
+
+        if !order.status.is_active() {
+            return Err(Error::api(format!("Order {} is not active", order_id)));
+        }
+
+        if quantity == 0 || quantity > order.remaining_quantity {
+            return Err(Error::api(format!("Invalid fill quantity {} for order {}", quantity, order_id)));
+        }
+
+        let filled_value = order.avg_fill_price.unwrap_or_default() * order.filled_quantity;
+        order.filled_quantity += quantity;
+        order.remaining_quantity -= quantity;
+        order.avg_fill_price = Some((filled_value + price * quantity) / Decimal::from(order.filled_quantity));
+        order.status = if order.remaining_quantity == 0 {
+            OrderStatus::Filled
+        } else {
+            OrderStatus::PartiallyFilled
+        };
+
+        let now = Utc::now();
+        order.updated_at = now;
+        order.last_filled_at = Some(now);
+
+        let order = order.clone();
+        let fill = FillRecord {
+            account_id: order.account_id.clone(),
+            order_no: order.order_no.clone(),
+            fill_no: (state.fills.len() + 1).to_string(),
+            symbol: order.request.symbol().to_string(),
+            action: order.request.action(),
+            quantity,
+            price,
+            filled_at: now,
+        };
+        state.fills.push(fill.clone());
+
+        Ok((order, fill))
+    }
+
+    fn order_mut<'a>(state: &'a mut State, order_id: &str) -> Result<&'a mut OrderResult> {
+        state.orders
+            .get_mut(order_id)
+            .ok_or_else(|| Error::api(format!("Order {} not found", order_id)))
+    }
+
+    /// Look up an order placed in `account`
+    fn account_order_mut<'a>(state: &'a mut State, account: &Account, order_id: &str) -> Result<&'a mut OrderResult> {
+        Self::order_mut(state, order_id)
+            .ok()
+            .filter(|order| order.account_id == account.account_id)
+            .ok_or_else(|| Error::api(format!("Order {} not found in account {}", order_id, account.account_id)))
+    }
+
+    /// Orders of an account placed on a trading day within `from..=to`, oldest first
+    fn orders_between(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<OrderResult>> {
+        self.ensure_logged_in()?;
+        self.account(account)?;
+
+        let mut orders: Vec<OrderResult> = self.state()?
+            .orders
+            .values()
+            .filter(|order| order.account_id == account.account_id)
+            .filter(|order| (from..=to).contains(&trading_day(order.created_at)))
+            .cloned()
+            .collect();
+        orders.sort_by(|a, b| a.order_no.cmp(&b.order_no));
+        Ok(orders)
+    }
+
+    /// Working orders that can be matched, i.e. not waiting for a condition
+    #[cfg(feature = "paper")]
+    pub(crate) fn working_orders(&self) -> Result<Vec<OrderResult>> {
+        Ok(self.state()?
+            .orders
+            .values()
+            .filter(|order| order.status.is_active() && !order.request.is_condition())
+            .cloned()
+            .collect())
+    }
+
+    /// Current state of an order in any account
+    #[cfg(feature = "paper")]
+    pub(crate) fn order(&self, order_id: &str) -> Result<OrderResult> {
+        let mut state = self.state()?;
+        Self::order_mut(&mut state, order_id).map(|order| order.clone())
+    }
+
+    /// Find the stored copy of an account
+    pub(crate) fn account(&self, account: &Account) -> Result<&Account> {
+        self.account_by_id(&account.account_id)
+    }
+
+    pub(crate) fn account_by_id(&self, account_id: &str) -> Result<&Account> {
+        self.accounts
+            .iter()
+            .find(|a| a.account_id == account_id)
+            .ok_or_else(|| Error::api(format!("Account {} not found", account_id)))
+    }
+
+    fn state(&self) -> Result<std::sync::MutexGuard<'_, State>> {
+        self.state
+            .lock()
+            .map_err(|_| Error::general("Memory backend lock poisoned"))
+    }
+
+    /// Check the session and store a new working order
+    fn submit(&self, account: &Account, request: OrderRequest) -> Result<OrderResult> {
+        self.account(account)?;
+
+        let mut state = self.state()?;
+        Self::check_session(&state)?;
+
+        state.next_order_id += 1;
+        let order_no = format!("M{:07}", state.next_order_id);
+        let seq_no = state.next_order_id.to_string();
+        let result = OrderResult::new(account.account_id.clone(), order_no.clone(), seq_no, OrderStatus::Working, request);
+        state.orders.insert(order_no, result.clone());
+        drop(state);
+
+        self.events.emit(TradeEvent::order(OrderEventKind::Placed, result.clone()));
+        Ok(result)
+    }
+
+    /// Wait for the simulated broker round trip
+    async fn round_trip(&self) {
+        if let Some(latency) = self.latency {
+            tokio::time::sleep(latency).await;
+        }
+    }
+
+    pub(crate) fn ensure_logged_in(&self) -> Result<()> {
+        Self::check_session(&*self.state()?)
+    }
+
+    fn check_session(state: &State) -> Result<()> {
+        let expired = state.expires_at
+            .map(|expires_at| expires_at <= Utc::now())
+            .unwrap_or(false);
+
+        if state.logged_in && !expired {
+            Ok(())
+        } else {
+            Err(Error::Unauthenticated)
+        }
+    }
+}
+
+impl Default for MemoryBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Backend for MemoryBackend {
+    fn events(&self) -> TradeEvents {
+        self.events.clone()
+    }
+
+    async fn login(&self, _credentials: &LoginCredentials) -> Result<LoginSession> {
+        let expires_at = self.session_ttl.map(|ttl| Utc::now() + ttl);
+
+        let mut state = self.state()?;
+        state.logged_in = true;
+        state.expires_at = expires_at;
+        state.logins += 1;
+
+        Ok(LoginSession {
+            accounts: self.accounts.clone(),
+            expires_at,
+        })
+    }
+
+    async fn logout(&self) -> Result<()> {
+        let mut state = self.state()?;
+        state.logged_in = false;
+        state.expires_at = None;
+        Ok(())
+    }
+
+    async fn exchange_realtime_token(&self) -> Result<RealtimeToken> {
+        let mut state = self.state()?;
+        Self::check_session(&state)?;
+
+        state.realtime_tokens += 1;
+        Ok(RealtimeToken::new(
+            format!("memory_realtime_token_{}", state.realtime_tokens),
+            self.realtime_token_ttl.map(|ttl| Utc::now() + ttl),
+        ))
+    }
+
+    async fn place_order(&self, account: &Account, order: &Order) -> Result<OrderResult> {
+        self.round_trip().await;
+        self.submit(account, OrderRequest::Stock(order.clone()))
+    }
+
+    async fn place_condition_order(&self, account: &Account, condition_order: &ConditionOrder) -> Result<OrderResult> {
+        self.round_trip().await;
+        self.submit(account, OrderRequest::Condition(condition_order.clone()))
+    }
+
+    async fn place_futopt_order(&self, account: &Account, order: &FutOptOrder) -> Result<OrderResult> {
+        self.round_trip().await;
+        self.submit(account, OrderRequest::FutOpt(order.clone()))
+    }
+
+    async fn place_futopt_condition_order(&self, account: &Account, condition_order: &FutOptConditionOrder) -> Result<OrderResult> {
+        self.round_trip().await;
+        self.submit(account, OrderRequest::FutOptCondition(condition_order.clone()))
+    }
+
+    async fn modify_price(&self, account: &Account, order: &OrderResult, price: Price) -> Result<OrderResult> {
+        self.ensure_logged_in()?;
+
+        let mut state = self.state()?;
+        let stored = Self::account_order_mut(&mut state, account, &order.order_no)?;
+        if !stored.status.is_active() {
+            return Err(Error::api(format!("Order {} is not active", order.order_no)));
+        }
+
+        stored.request.set_price(price);
+        stored.updated_at = Utc::now();
+        let modified = stored.clone();
+        drop(state);
+
+        self.events.emit(TradeEvent::order(OrderEventKind::PriceModified, modified.clone()));
+        Ok(modified)
+    }
+
+    async fn modify_quantity(&self, account: &Account, order: &OrderResult, quantity: u32) -> Result<OrderResult> {
+        self.ensure_logged_in()?;
+
+        let mut state = self.state()?;
+        let stored = Self::account_order_mut(&mut state, account, &order.order_no)?;
+        if !stored.status.is_active() {
+            return Err(Error::api(format!("Order {} is not active", order.order_no)));
+        }
+
+        if quantity < stored.filled_quantity {
+            return Err(Error::api(format!("Order {} already has {} filled", order.order_no, stored.filled_quantity)));
+        }
+
+        stored.request.set_quantity(quantity);
+        stored.remaining_quantity = quantity - stored.filled_quantity;
+        if stored.remaining_quantity == 0 {
+            stored.status = if stored.filled_quantity > 0 {
+                OrderStatus::Filled
+            } else {
+                OrderStatus::Cancelled
+            };
+        }
+        stored.updated_at = Utc::now();
+        let modified = stored.clone();
+        drop(state);
+
+        self.events.emit(TradeEvent::order(OrderEventKind::QuantityModified, modified.clone()));
+        Ok(modified)
+    }
+
+    async fn cancel_order(&self, account: &Account, order_id: &str) -> Result<()> {
+        self.round_trip().await;
+        self.ensure_logged_in()?;
+
+        let mut state = self.state()?;
+        let order = Self::account_order_mut(&mut state, account, order_id)?;
+        if !order.status.is_active() {
+            return Err(Error::api(format!("Order {} is not active", order_id)));
+        }
+
+        order.status = OrderStatus::Cancelled;
+        order.updated_at = Utc::now();
+        let cancelled = order.clone();
+        drop(state);
+
+        self.events.emit(TradeEvent::order(OrderEventKind::Cancelled, cancelled));
+        Ok(())
+    }
+
+    async fn get_order_status(&self, account: &Account, order_id: &str) -> Result<OrderResult> {
+        self.ensure_logged_in()?;
+
+        let mut state = self.state()?;
+        Self::account_order_mut(&mut state, account, order_id).map(|order| order.clone())
+    }
+
+    async fn get_order_results(&self, account: &Account) -> Result<Vec<OrderResult>> {
+        let today = trading_day(Utc::now());
+        self.orders_between(account, today, today)
+    }
+
+    async fn get_order_history(
+        &self,
+        account: &Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        status: Option<HistoryStatus>,
+    ) -> Result<Vec<OrderResult>> {
+        let mut orders = self.orders_between(account, from, to)?;
+        if let Some(status) = status {
+            orders.retain(|order| status.matches(order.status));
+        }
+        Ok(orders)
+    }
+
+    async fn get_filled_history(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<FillRecord>> {
+        self.ensure_logged_in()?;
+        self.account(account)?;
+
+        let fills = self.state()?
+            .fills
+            .iter()
+            .filter(|fill| fill.account_id == account.account_id)
+            .filter(|fill| (from..=to).contains(&trading_day(fill.filled_at)))
+            .cloned()
+            .collect();
+        Ok(fills)
+    }
+
+    async fn get_realized_pnl(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<RealizedPnl>> {
+        self.account(account)?;
+        let state = self.state()?;
+        Self::check_session(&state)?;
+
+        let mut fills: Vec<&FillRecord> = state.fills
+            .iter()
+            .filter(|fill| fill.account_id == account.account_id)
+            .filter(|fill| trading_day(fill.filled_at) <= to)
+            .collect();
+        fills.sort_by_key(|fill| fill.filled_at);
+
+        let mut inventories: HashMap<&str, Inventory> = HashMap::new();
+        let mut realized: BTreeMap<&str, RealizedPnl> = BTreeMap::new();
+
+        for fill in fills {
+            let request = state.orders.get(&fill.order_no).map(|order| &order.request);
+            let (market_type, charges) = match request {
+                Some(request) if request.is_futopt() => (MarketType::Future, TradeCharges::default()),
+                _ => {
+                    let trading_type = match request {
+                        Some(OrderRequest::Stock(order)) => order.trading_type,
+                        Some(OrderRequest::Condition(condition_order)) => condition_order.order.trading_type,
+                        _ => TradingType::Normal,
+                    };
+                    let value = fill.price * fill.quantity;
+                    (MarketType::Stock, TradeCharges::stock(fill.action, value, trading_type))
+                }
+            };
+
+            let quantity = fill.quantity as i64;
+            let direction = match fill.action {
+                BSAction::Buy => 1,
+                BSAction::Sell => -1,
+            };
+            let inventory = inventories.entry(&fill.symbol).or_default();
+
+            // Close against the opposite side first, then open with the rest
+            let closing = if inventory.quantity.signum() == -direction {
+                inventory.quantity.abs().min(quantity)
+            } else {
+                0
+            };
+
+            if closing > 0 {
+                let closed = Decimal::from(closing);
+                let share = closed / Decimal::from(inventory.quantity.abs());
+                let (cost, proceeds) = if inventory.quantity > 0 {
+                    (inventory.avg_cost * closed, fill.price * closed)
+                } else {
+                    (fill.price * closed, inventory.avg_cost * closed)
+                };
+                let released = inventory.charges.scale(share) + charges.scale(closed / Decimal::from(quantity));
+
+                inventory.charges = inventory.charges.scale(Decimal::ONE - share);
+                inventory.quantity += direction * closing;
+                if inventory.quantity == 0 {
+                    inventory.avg_cost = Decimal::ZERO;
+                }
+
+                if trading_day(fill.filled_at) >= from {
+                    let pnl = realized.entry(&fill.symbol).or_insert_with(|| RealizedPnl {
+                        symbol: fill.symbol.clone(),
+                        market_type,
+                        quantity: 0,
+                        cost: Decimal::ZERO,
+                        proceeds: Decimal::ZERO,
+                        charges: TradeCharges::default(),
+                        gross_pnl: Decimal::ZERO,
+                        net_pnl: Decimal::ZERO,
+                    });
+                    pnl.quantity += closing as u64;
+                    pnl.cost += cost;
+                    pnl.proceeds += proceeds;
+                    pnl.charges += released;
+                }
+            }
+
+            let opening = quantity - closing;
+            if opening > 0 {
+                let held = inventory.quantity.abs();
+                inventory.avg_cost = (inventory.avg_cost * held + fill.price * opening) / Decimal::from(held + opening);
+                inventory.quantity += direction * opening;
+                inventory.charges += charges.scale(Decimal::from(opening) / Decimal::from(quantity));
+            }
+        }
+
+        Ok(realized
+            .into_values()
+            .map(|mut pnl| {
+                pnl.gross_pnl = pnl.proceeds - pnl.cost;
+                pnl.net_pnl = pnl.gross_pnl - pnl.charges.total();
+                pnl
+            })
+            .collect())
+    }
+
+    async fn get_unrealized_pnl(&self, account: &Account) -> Result<Vec<UnrealizedPnl>> {
+        let positions = self.get_positions(account).await?;
+        Ok(positions.into_iter().map(UnrealizedPnl::from_position).collect())
+    }
+
+    async fn get_account_balance(&self, account: &Account) -> Result<Balance> {
+        let account = self.account(account)?;
+        let state = self.state()?;
+        Self::check_session(&state)?;
+
+        let today = trading_day(Utc::now());
+        let dates = [today, add_trading_days(today, 1), add_trading_days(today, 2)];
+        let mut amounts = [Decimal::ZERO; 3];
+        let mut bought_today = Decimal::ZERO;
+
+        let fills = state.fills
+            .iter()
+            .filter(|fill| account.is_stock() && fill.account_id == account.account_id);
+
+        for fill in fills {
+            let traded = trading_day(fill.filled_at);
+            let value = fill.price * fill.quantity;
+            let amount = match fill.action {
+                BSAction::Buy => -value,
+                BSAction::Sell => value,
+            };
+
+            if traded == today && fill.action == BSAction::Buy {
+                bought_today += value;
+            }
+
+            // Fills settled before today are already in the bank balance
+            let settles = add_trading_days(traded, 2);
+            if let Some(index) = dates.iter().position(|date| *date == settles) {
+                amounts[index] += amount;
+            }
+        }
+
+        let settlement = |index: usize| Settlement { date: dates[index], amount: amounts[index] };
+        Ok(Balance {
+            account_id: account.account_id.clone(),
+            currency: account.currency.clone(),
+            bank_balance: self.bank_balances
+                .get(&account.account_id)
+                .copied()
+                .or(account.total_balance)
+                .unwrap_or_default(),
+            buying_power: account.available_balance.unwrap_or_default() - bought_today,
+            t0: settlement(0),
+            t1: settlement(1),
+            t2: settlement(2),
+        })
+    }
+
+    async fn get_futopt_equity(&self, account: &Account) -> Result<FutOptEquity> {
+        let positions = self.get_futopt_positions(account).await?;
+        let account = self.account(account)?;
+
+        let unrealized_pnl = positions.unrealized_pnl();
+        let equity = account.total_balance.unwrap_or_default() + unrealized_pnl;
+        let (initial_margin, maintenance_margin) = self.futopt_margins
+            .get(&account.account_id)
+            .copied()
+            .unwrap_or_default();
+
+        Ok(FutOptEquity {
+            account_id: account.account_id.clone(),
+            currency: account.currency.clone(),
+            equity,
+            initial_margin,
+            maintenance_margin,
+            excess_margin: equity - initial_margin,
+            risk_indicator: (maintenance_margin.is_positive()).then(|| (equity / maintenance_margin).to_f64()),
+            unrealized_pnl,
+        })
+    }
+
+    async fn get_futopt_positions(&self, account: &Account) -> Result<FutOptPositions> {
+        self.ensure_logged_in()?;
+        self.account(account)?;
+
+        let single = self.positions
+            .get(&account.account_id)
+            .map(|positions| positions.iter().filter(|position| !position.is_stock()).cloned().collect())
+            .unwrap_or_default();
+        let combined = self.combined_positions.get(&account.account_id).cloned().unwrap_or_default();
+
+        Ok(FutOptPositions { single, combined })
+    }
+
+    async fn get_margin_quota(&self, account: &Account, symbol: &str) -> Result<MarginQuota> {
+        self.ensure_logged_in()?;
+        self.account(account)?;
+
+        let key = (account.account_id.clone(), symbol.to_string());
+        Ok(self.margin_quotas.get(&key).cloned().unwrap_or_else(|| MarginQuota {
+            symbol: symbol.to_string(),
+            margin_available: false,
+            margin_quota: 0,
+            margin_ratio: 0.0,
+            short_available: false,
+            short_quota: 0,
+            short_ratio: 0.0,
+            maintenance_ratio: None,
+        }))
+    }
+
+    async fn get_positions(&self, account: &Account) -> Result<Vec<Position>> {
+        self.ensure_logged_in()?;
+        self.account(account)?;
+        Ok(self.positions.get(&account.account_id).cloned().unwrap_or_default())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,158 @@
+//! Trading backends that [`FubonSDK`](crate::FubonSDK) delegates to.
+//!
+//! Implementations are selected with cargo features:
+//! - `http` (default): pure-Rust HTTPS client for the trading API
+//! - `memory`: in-memory backend for tests and demos
+//! - `paper`: paper trading against live or replayed market data
+//!
+//! There is no backend calling the vendor `_fubon_neo` library: it is a
+//! CPython extension module without a C entry point to bind to.
+
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "memory")]
+pub mod memory;
+#[cfg(feature = "paper")]
+pub mod paper;
+
+#[cfg(feature = "http")]
+pub use http::HttpBackend;
+#[cfg(feature = "memory")]
+pub use memory::MemoryBackend;
+#[cfg(feature = "paper")]
+pub use paper::PaperBackend;
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::Result;
+use crate::decimal::Price;
+use crate::events::TradeEvents;
+use crate::pnl::{RealizedPnl, UnrealizedPnl};
+use crate::session::{LoginSession, RealtimeToken};
+use crate::types::*;
+
+#[cfg(not(any(feature = "http", feature = "memory")))]
+compile_error!("At least one trading backend feature (`http` or `memory`) must be enabled");
+
+/// Transport backend used by the SDK for trading operations.
+///
+/// Backends own their session state: after a successful `login` the other
+/// methods act on behalf of the logged in user, in the account passed to them.
+#[async_trait]
+pub trait Backend: Send + Sync {
+    /// Trading events pushed by this backend
+    fn events(&self) -> TradeEvents;
+
+    /// Authenticate and return the account list and session expiry.
+    ///
+    /// Calls made with a session the broker no longer accepts must fail with
+    /// [`Error::Unauthenticated`](crate::Error::Unauthenticated) so that the
+    /// SDK can log in again.
+    async fn login(&self, credentials: &LoginCredentials) -> Result<LoginSession>;
+
+    /// End the session
+    async fn logout(&self) -> Result<()>;
+
+    /// Exchange the login session for a realtime market data token
+    async fn exchange_realtime_token(&self) -> Result<RealtimeToken>;
+
+    /// Submit an order
+    async fn place_order(&self, account: &Account, order: &Order) -> Result<OrderResult>;
+
+    /// Submit a conditional order
+    async fn place_condition_order(&self, account: &Account, condition_order: &ConditionOrder) -> Result<OrderResult>;
+
+    /// Submit a future/option order
+    async fn place_futopt_order(&self, account: &Account, order: &FutOptOrder) -> Result<OrderResult>;
+
+    /// Submit a future/option conditional order
+    async fn place_futopt_condition_order(&self, account: &Account, condition_order: &FutOptConditionOrder) -> Result<OrderResult>;
+
+    /// Change the price of a working order
+    async fn modify_price(&self, account: &Account, order: &OrderResult, price: Price) -> Result<OrderResult>;
+
+    /// Change the total quantity of a working order
+    async fn modify_quantity(&self, account: &Account, order: &OrderResult, quantity: u32) -> Result<OrderResult>;
+
+    /// Cancel an order
+    async fn cancel_order(&self, account: &Account, order_id: &str) -> Result<()>;
+
+    /// Get the current state of an order
+    async fn get_order_status(&self, account: &Account, order_id: &str) -> Result<OrderResult>;
+
+    /// Get today's orders
+    async fn get_order_results(&self, account: &Account) -> Result<Vec<OrderResult>>;
+
+    /// Get orders placed between two trading days (inclusive)
+    async fn get_order_history(
+        &self,
+        account: &Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        status: Option<HistoryStatus>,
+    ) -> Result<Vec<OrderResult>>;
+
+    /// Get fills between two trading days (inclusive)
+    async fn get_filled_history(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<FillRecord>>;
+
+    /// Get realized profit and loss per symbol between two trading days (inclusive)
+    async fn get_realized_pnl(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<RealizedPnl>>;
+
+    /// Get unrealized profit and loss of the open positions
+    async fn get_unrealized_pnl(&self, account: &Account) -> Result<Vec<UnrealizedPnl>>;
+
+    /// Get bank balance, buying power and pending settlements of an account
+    async fn get_account_balance(&self, account: &Account) -> Result<Balance>;
+
+    /// Get the equity and margin of a futures/options account
+    async fn get_futopt_equity(&self, account: &Account) -> Result<FutOptEquity>;
+
+    /// Get futures/options positions, with combined positions separated
+    async fn get_futopt_positions(&self, account: &Account) -> Result<FutOptPositions>;
+
+    /// Get the margin and short sale quota of a symbol
+    async fn get_margin_quota(&self, account: &Account, symbol: &str) -> Result<MarginQuota>;
+
+    /// Get positions
+    async fn get_positions(&self, account: &Account) -> Result<Vec<Position>>;
+}
+
+/// Acknowledgement returned by the broker when an order is accepted
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderAck {
+    /// Broker order number
+    pub order_no: String,
+    /// Order sequence number
+    #[serde(default)]
+    pub seq_no: String,
+    /// Initial order status
+    #[serde(default = "OrderAck::default_status")]
+    pub status: OrderStatus,
+}
+
+impl OrderAck {
+    fn default_status() -> OrderStatus {
+        OrderStatus::Working
+    }
+
+    /// Combine the acknowledgement with the submitted request
+    pub fn into_result(self, account: &Account, request: OrderRequest) -> OrderResult {
+        OrderResult::new(account.account_id.clone(), self.order_no, self.seq_no, self.status, request)
+    }
+}
+
+/// Backend used by [`FubonSDK::new`](crate::FubonSDK::new), chosen by enabled features
+pub fn default_backend() -> Arc<dyn Backend> {
+    #[cfg(feature = "http")]
+    {
+        Arc::new(HttpBackend::new(crate::trade::TradeConfig::new()))
+    }
+
+    #[cfg(all(not(feature = "http"), feature = "memory"))]
+    {
+        Arc::new(MemoryBackend::new())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,725 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, Error};
+use crate::backend::Backend;
+use crate::backend::memory::{add_trading_days, MemoryBackend};
+use crate::constants::STOCK_LOT_SIZE;
+use crate::contract::ContractSpec;
+use crate::decimal::{Amount, Decimal, Price};
+use crate::events::{FillEvent, TradeEvent, TradeEvents};
+use crate::market_data::websocket::{market_data_message, EventHandler, WebSocketEvent};
+use crate::pnl::{RealizedPnl, TradeCharges, UnrealizedPnl};
+use crate::session::{LoginSession, RealtimeToken};
+use crate::types::*;
+
+/// Price level of an order book
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub price: Price,
+    /// Quantity at this price, in feed units
+    pub size: u64,
+}
+
+impl BookLevel {
+    pub fn new(price: Price, size: u64) -> Self {
+        Self { price, size }
+    }
+}
+
+/// Latest market data of one symbol
+#[derive(Debug, Clone, Default)]
+struct Quote {
+    /// Best bid first
+    bids: Vec<BookLevel>,
+    /// Best ask first
+    asks: Vec<BookLevel>,
+    last: Option<Price>,
+}
+
+/// Simulated holding of one symbol
+#[derive(Debug)]
+struct Holding {
+    market_type: MarketType,
+    /// TWD per price point
+    multiplier: Decimal,
+    /// Signed quantity, positive when long
+    quantity: i64,
+    avg_cost: Price,
+}
+
+impl Holding {
+    /// Apply a fill and return the profit or loss of the part that closed
+    fn apply(&mut self, action: BSAction, quantity: i64, price: Price) -> Amount {
+        let signed = match action {
+            BSAction::Buy => quantity,
+            BSAction::Sell => -quantity,
+        };
+
+        let closed = if self.quantity * signed < 0 {
+            signed.abs().min(self.quantity.abs())
+        } else {
+            0
+        };
+        let pnl = (price - self.avg_cost) * closed * self.quantity.signum();
+
+        let opened = signed.abs() - closed;
+        if opened > 0 {
+            let remaining = self.quantity.abs() - closed;
+            self.avg_cost = if remaining == 0 {
+                price
+            } else {
+                (self.avg_cost * remaining + price * opened) / Decimal::from(remaining + opened)
+            };
+        }
+        self.quantity += signed;
+        pnl
+    }
+}
+
+/// Simulated cash and holdings of an account
+#[derive(Debug)]
+struct Portfolio {
+    cash: Amount,
+    holdings: HashMap<String, Holding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TradeData {
+    symbol: String,
+    price: Price,
+    size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BookData {
+    symbol: String,
+    #[serde(default)]
+    bids: Vec<BookLevel>,
+    #[serde(default)]
+    asks: Vec<BookLevel>,
+}
+
+/// Paper-trading backend filling orders against market data.
+///
+/// Orders, fills and trading events are kept as in [`MemoryBackend`], which
+/// also answers order queries and realized profit and loss. Each account
+/// starts with its `available_balance` as cash and no holdings; stock fills
+/// pay standard commission and tax and settle into cash immediately, while
+/// futures and options carry no charges and settle their profit and loss into
+/// cash at the product's contract multiplier (see
+/// [`with_contract_multiplier`](Self::with_contract_multiplier)). Unrealized
+/// profit and loss is in TWD; realized profit and loss of futures and options
+/// is reported in price points, as by [`MemoryBackend`].
+///
+/// Market data comes from the trade and book channels of a
+/// [`WebSocketClient`](crate::market_data::websocket::WebSocketClient) the
+/// backend is registered with as an event handler, or is replayed with
+/// [`on_trade`](Self::on_trade) and [`on_book`](Self::on_book). Working
+/// orders are matched by price, then time: a book update fills them at the
+/// opposite levels' prices and a trade print at the trade price, each up to
+/// the displayed size. Orders that can trade are matched on placement; the
+/// unfilled part of an IOC order is cancelled and an FOK order is cancelled
+/// unless it can fill completely. Stock buys are rejected beyond the
+/// account's cash less its working buys, and futures/options of products
+/// without a contract multiplier are rejected. Condition orders are not supported.
+///
+/// Fill events are published after the matching locks are released, so
+/// trading event handlers may call back into the backend.
+pub struct PaperBackend {
+    orders: MemoryBackend,
+    portfolios: Mutex<HashMap<String, Portfolio>>,
+    quotes: Mutex<HashMap<String, Quote>>,
+    /// Time priority of working orders, renewed when their price changes;
+    /// held while fills are worked out so that updates are matched one at a time
+    priorities: Mutex<HashMap<String, u64>>,
+    stock_feed_lot_size: u64,
+    /// TWD per price point of futures/options products, by monthly product code
+    contract_multipliers: HashMap<String, Decimal>,
+}
+
+impl PaperBackend {
+    /// Create a backend with the demo accounts of [`MemoryBackend::new`]
+    pub fn new() -> Self {
+        Self {
+            orders: MemoryBackend::new(),
+            portfolios: Mutex::new(HashMap::new()),
+            quotes: Mutex::new(HashMap::new()),
+            priorities: Mutex::new(HashMap::new()),
+            stock_feed_lot_size: STOCK_LOT_SIZE,
+            contract_multipliers: HashMap::new(),
+        }
+    }
+
+    pub fn with_accounts(mut self, accounts: Vec<Account>) -> Self {
+        self.orders = self.orders.with_accounts(accounts);
+        self
+    }
+
+    /// Set the starting cash of an account
+    pub fn with_cash(mut self, account_id: &str, cash: Amount) -> Self {
+        self.orders = self.orders.with_balance(account_id, cash);
+        self
+    }
+
+    /// Set the number of shares in a unit of stock trade and book sizes.
+    ///
+    /// Defaults to a board lot, as regular-session feeds report sizes in lots;
+    /// use 1 for odd-lot feeds. Futures and options sizes are in contracts.
+    pub fn with_stock_feed_lot_size(mut self, shares: u64) -> Self {
+        self.stock_feed_lot_size = shares.max(1);
+        self
+    }
+
+    /// Value futures/options of `product` at `multiplier` TWD per price point,
+    /// in addition to the built-in TAIFEX index products
+    pub fn with_contract_multiplier(mut self, product: &str, multiplier: Decimal) -> Self {
+        self.contract_multipliers.insert(product.to_string(), multiplier);
+        self
+    }
+
+    /// Replay a trade of `size` at `price`, filling working orders it crosses
+    pub fn on_trade(&self, symbol: &str, price: Price, size: u64) -> Result<()> {
+        self.quotes()?.entry(symbol.to_string()).or_default().last = Some(price);
+        self.match_symbol(symbol, Some(BookLevel::new(price, size)))
+    }
+
+    /// Replay an order book update, filling working orders it crosses
+    pub fn on_book(&self, symbol: &str, mut bids: Vec<BookLevel>, mut asks: Vec<BookLevel>) -> Result<()> {
+        bids.sort_by_key(|level| std::cmp::Reverse(level.price));
+        asks.sort_by_key(|level| level.price);
+
+        {
+            let mut quotes = self.quotes()?;
+            let quote = quotes.entry(symbol.to_string()).or_default();
+            quote.bids = bids;
+            quote.asks = asks;
+        }
+        self.match_symbol(symbol, None)
+    }
+
+    /// Last traded price of a symbol
+    pub fn last_price(&self, symbol: &str) -> Option<Price> {
+        self.quotes().ok()?.get(symbol)?.last
+    }
+
+    /// Simulated cash of an account
+    pub fn cash(&self, account: &Account) -> Result<Amount> {
+        let account = self.orders.account(account)?;
+        Ok(self.portfolio(account)?.get(&account.account_id).map_or(Decimal::ZERO, |portfolio| portfolio.cash))
+    }
+
+    /// Apply a streaming message from the trades or books channel
+    fn on_message(&self, text: &str) -> Result<()> {
+        let Some((channel, data)) = market_data_message(text) else {
+            return Ok(());
+        };
+
+        match channel.as_str() {
+            "trades" => {
+                let trade: TradeData = serde_json::from_value(data)?;
+                self.on_trade(&trade.symbol, trade.price, trade.size)
+            }
+            "books" => {
+                let book: BookData = serde_json::from_value(data)?;
+                self.on_book(&book.symbol, book.bids, book.asks)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Match the working orders of a symbol against its book, or against a trade print
+    fn match_symbol(&self, symbol: &str, trade: Option<BookLevel>) -> Result<()> {
+        // Fills are executed once the locks are released, as fill events call
+        // trading event handlers that may call back into the backend
+        let fills = self.find_fills(symbol, trade)?;
+
+        for (order_no, quantity, price) in fills {
+            // A handler of an earlier fill may have changed the order
+            let order = self.orders.order(&order_no)?;
+            let quantity = quantity.min(order.remaining_quantity as u64);
+            if order.status.is_active() && quantity > 0 {
+                self.execute(&order, quantity, price)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills of the working orders of a symbol, as order number, quantity and price
+    fn find_fills(&self, symbol: &str, trade: Option<BookLevel>) -> Result<Vec<(String, u64, Price)>> {
+        let priorities = self.priorities()?;
+
+        let mut orders: Vec<OrderResult> = self.orders
+            .working_orders()?
+            .into_iter()
+            .filter(|order| order.request.symbol() == symbol)
+            .collect();
+        let Some(first) = orders.first() else {
+            return Ok(Vec::new());
+        };
+        let lot_size = if first.request.is_futopt() { 1 } else { self.stock_feed_lot_size };
+
+        // Price priority, market orders first, then time priority
+        let priority = |order: &OrderResult| priorities.get(&order.order_no).copied().unwrap_or(u64::MAX);
+        orders.sort_by(|a, b| {
+            let by_price = match (limit_of(a), limit_of(b)) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (Some(a_price), Some(b_price)) => match a.request.action() {
+                    BSAction::Buy => b_price.cmp(&a_price),
+                    BSAction::Sell => a_price.cmp(&b_price),
+                },
+            };
+            (a.request.action() as u8).cmp(&(b.request.action() as u8))
+                .then(by_price)
+                .then(priority(a).cmp(&priority(b)))
+        });
+
+        let mut quotes = self.quotes()?;
+        let quote = quotes.entry(symbol.to_string()).or_default();
+        let mut trade_sizes = trade.map(|level| [level.size * lot_size; 2]);
+
+        let mut fills = Vec::new();
+        for order in orders {
+            let mut remaining = order.remaining_quantity as u64;
+            let action = order.request.action();
+            let limit = limit_of(&order);
+            let crosses = |price: Price| crosses(action, limit, price);
+
+            match (trade, trade_sizes.as_mut()) {
+                (Some(level), Some(sizes)) => {
+                    let available = &mut sizes[action as usize];
+                    if crosses(level.price) && *available > 0 {
+                        let quantity = remaining.min(*available);
+                        *available -= quantity;
+                        fills.push((order.order_no.clone(), quantity, level.price));
+                    }
+                }
+                _ => {
+                    let levels = match action {
+                        BSAction::Buy => &mut quote.asks,
+                        BSAction::Sell => &mut quote.bids,
+                    };
+                    for level in levels.iter_mut().take_while(|level| crosses(level.price)) {
+                        if remaining == 0 {
+                            break;
+                        }
+                        let quantity = remaining.min(level.size * lot_size);
+                        if quantity == 0 {
+                            continue;
+                        }
+                        // Liquidity taken by simulated fills stays taken until the next update
+                        level.size -= quantity.div_ceil(lot_size);
+                        remaining -= quantity;
+                        fills.push((order.order_no.clone(), quantity, level.price));
+                    }
+                    levels.retain(|level| level.size > 0);
+                }
+            }
+        }
+
+        Ok(fills)
+    }
+
+    /// Quantity of an order that the current book could fill immediately
+    fn fillable(&self, request: &OrderRequest, limit: Option<Price>) -> Result<u64> {
+        let quotes = self.quotes()?;
+        let Some(quote) = quotes.get(request.symbol()) else {
+            return Ok(0);
+        };
+        let lot_size = if request.is_futopt() { 1 } else { self.stock_feed_lot_size };
+
+        let levels = match request.action() {
+            BSAction::Buy => &quote.asks,
+            BSAction::Sell => &quote.bids,
+        };
+        let size: u64 = levels
+            .iter()
+            .take_while(|level| crosses(request.action(), limit, level.price))
+            .map(|level| level.size)
+            .sum();
+        Ok(size * lot_size)
+    }
+
+    /// Record a fill of a working order, update the account's cash and holdings
+    /// and then publish the fill
+    fn execute(&self, order: &OrderResult, quantity: u64, price: Price) -> Result<()> {
+        let multiplier = self.multiplier(&order.request)?;
+        let (filled, fill) = self.orders.record_fill(&order.order_no, quantity as u32, price)?;
+
+        {
+            let account = self.orders.account_by_id(&order.account_id)?;
+            let mut portfolios = self.portfolio(account)?;
+            let portfolio = portfolios.get_mut(&account.account_id).expect("portfolio initialised");
+            settle(portfolio, order, quantity, price, multiplier);
+        }
+
+        self.orders.events().emit(TradeEvent::Filled(FillEvent { fill, order: filled }));
+        Ok(())
+    }
+
+    /// TWD per price point of an order: 1 for stocks, the contract multiplier for futures/options
+    fn multiplier(&self, request: &OrderRequest) -> Result<Decimal> {
+        if !request.is_futopt() {
+            return Ok(Decimal::ONE);
+        }
+
+        let contract = ContractSpec::parse(request.symbol())?;
+        self.contract_multipliers
+            .get(contract.monthly_product())
+            .copied()
+            .or_else(|| contract.multiplier())
+            .ok_or_else(|| Error::api(format!("No contract multiplier for {} in paper trading", contract.monthly_product())))
+    }
+
+    /// Cash committed to working stock buys of an account
+    fn reserved_cash(&self, account: &Account) -> Result<Amount> {
+        let orders = self.orders.working_orders()?;
+        let mut reserved = Decimal::ZERO;
+        for order in orders.iter().filter(|order| order.account_id == account.account_id) {
+            if let OrderRequest::Stock(stock) = &order.request {
+                if stock.action == BSAction::Buy {
+                    let price = self.reference_price(&order.request)?;
+                    let value = price * order.remaining_quantity;
+                    reserved += value + TradeCharges::stock(BSAction::Buy, value, stock.trading_type).total();
+                }
+            }
+        }
+        Ok(reserved)
+    }
+
+    /// Price an order is expected to trade at: its limit, or the best opposite
+    /// price or last trade for market orders
+    fn reference_price(&self, request: &OrderRequest) -> Result<Price> {
+        if let Some(price) = request.price().filter(|_| !request.is_market()) {
+            return Ok(price);
+        }
+
+        let quotes = self.quotes()?;
+        let quote = quotes.get(request.symbol());
+        let best = quote.and_then(|quote| match request.action() {
+            BSAction::Buy => quote.asks.first(),
+            BSAction::Sell => quote.bids.first(),
+        });
+        best.map(|level| level.price)
+            .or_else(|| quote.and_then(|quote| quote.last))
+            .ok_or_else(|| Error::api(format!("No market price for {}", request.symbol())))
+    }
+
+    /// Check a stock buy against the account's free cash
+    fn check_cash(&self, account: &Account, order: &Order) -> Result<()> {
+        if order.action != BSAction::Buy {
+            return Ok(());
+        }
+
+        let request = OrderRequest::Stock(order.clone());
+        let value = self.reference_price(&request)? * order.quantity;
+        let required = value + TradeCharges::stock(BSAction::Buy, value, order.trading_type).total();
+        let reserved = self.reserved_cash(account)?;
+        let available = self.cash(account)? - reserved;
+
+        if required > available {
+            return Err(Error::api(format!(
+                "Insufficient cash for {} {}: {:.0} required, {:.0} available",
+                order.quantity, order.symbol, required, available
+            )));
+        }
+        Ok(())
+    }
+
+    /// Place an order, match what can trade now and apply its time in force
+    async fn submit(&self, account: &Account, placed: OrderResult, time_in_force: TimeInForce) -> Result<OrderResult> {
+        self.priorities()?.insert(placed.order_no.clone(), next_priority());
+
+        let fill_or_kill = time_in_force == TimeInForce::Fok
+            && self.fillable(&placed.request, limit_of(&placed))? < placed.request.quantity() as u64;
+        if !fill_or_kill {
+            self.match_symbol(placed.request.symbol(), None)?;
+        }
+
+        let order = self.orders.get_order_status(account, &placed.order_no).await?;
+        if order.status.is_active() && matches!(time_in_force, TimeInForce::Ioc | TimeInForce::Fok) {
+            self.orders.cancel_order(account, &order.order_no).await?;
+            return self.orders.get_order_status(account, &order.order_no).await;
+        }
+        Ok(order)
+    }
+
+    /// Cash and holdings of all accounts, creating the account's on first use
+    fn portfolio(&self, account: &Account) -> Result<MutexGuard<'_, HashMap<String, Portfolio>>> {
+        let mut portfolios = self.portfolios.lock().map_err(|_| Error::general("Paper backend lock poisoned"))?;
+        portfolios.entry(account.account_id.clone()).or_insert_with(|| Portfolio {
+            cash: account.available_balance.unwrap_or_default(),
+            holdings: HashMap::new(),
+        });
+        Ok(portfolios)
+    }
+
+    fn quotes(&self) -> Result<MutexGuard<'_, HashMap<String, Quote>>> {
+        self.quotes.lock().map_err(|_| Error::general("Paper backend lock poisoned"))
+    }
+
+    fn priorities(&self) -> Result<MutexGuard<'_, HashMap<String, u64>>> {
+        self.priorities.lock().map_err(|_| Error::general("Paper backend lock poisoned"))
+    }
+
+    /// Simulated holdings of an account valued in TWD at the last traded prices
+    fn holdings(&self, account: &Account) -> Result<Vec<Position>> {
+        self.orders.ensure_logged_in()?;
+        let account = self.orders.account(account)?;
+
+        let mut positions: Vec<(Position, Decimal)> = {
+            let portfolios = self.portfolio(account)?;
+            portfolios[&account.account_id].holdings
+                .iter()
+                .map(|(symbol, holding)| (Position {
+                    symbol: symbol.clone(),
+                    market_type: holding.market_type,
+                    action: if holding.quantity > 0 { BSAction::Buy } else { BSAction::Sell },
+                    trading_type: TradingType::Normal,
+                    quantity: holding.quantity.unsigned_abs(),
+                    avg_cost: holding.avg_cost,
+                    last_price: holding.avg_cost,
+                    unrealized_pnl: Decimal::ZERO,
+                    today_buy_quantity: 0,
+                    today_sell_quantity: 0,
+                }, holding.multiplier))
+                .collect()
+        };
+
+        for (position, multiplier) in &mut positions {
+            position.last_price = self.last_price(&position.symbol).unwrap_or(position.avg_cost);
+            let direction = if position.action == BSAction::Buy { 1 } else { -1 };
+            position.unrealized_pnl = (position.last_price - position.avg_cost) * position.quantity * direction * *multiplier;
+        }
+        let mut positions: Vec<Position> = positions.into_iter().map(|(position, _)| position).collect();
+        positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        Ok(positions)
+    }
+}
+
+impl Default for PaperBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventHandler for PaperBackend {
+    fn handle_event(&self, event: WebSocketEvent) {
+        if let WebSocketEvent::Message(text) = event {
+            // Messages that are not market data are skipped inside on_message
+            if let Err(e) = self.on_message(&text) {
+                tracing::warn!("Paper trading could not apply market data message: {}", e);
+            }
+        }
+    }
+}
+
+/// Apply a fill of `quantity` at `price` to the cash and holdings of a portfolio
+fn settle(portfolio: &mut Portfolio, order: &OrderResult, quantity: u64, price: Price, multiplier: Decimal) {
+    let action = order.request.action();
+    let market_type = market_type_of(&order.request);
+    let holding = portfolio.holdings
+        .entry(order.request.symbol().to_string())
+        .or_insert(Holding { market_type, multiplier, quantity: 0, avg_cost: Decimal::ZERO });
+    let realized = holding.apply(action, quantity as i64, price);
+    if holding.quantity == 0 {
+        portfolio.holdings.remove(order.request.symbol());
+    }
+
+    match &order.request {
+        OrderRequest::Stock(stock) => {
+            let value = price * quantity;
+            let charges = TradeCharges::stock(action, value, stock.trading_type).total();
+            portfolio.cash += match action {
+                BSAction::Buy => -value - charges,
+                BSAction::Sell => value - charges,
+            };
+        }
+        _ => portfolio.cash += realized * multiplier,
+    }
+}
+
+/// Worst price an order accepts, `None` for market orders that accept any price
+fn limit_of(order: &OrderResult) -> Option<Price> {
+    order.request.price().filter(|_| !order.request.is_market())
+}
+
+/// Whether an order on the `action` side with `limit` can trade at `price`
+fn crosses(action: BSAction, limit: Option<Price>, price: Price) -> bool {
+    match (action, limit) {
+        (_, None) => true,
+        (BSAction::Buy, Some(limit)) => price <= limit,
+        (BSAction::Sell, Some(limit)) => price >= limit,
+    }
+}
+
+fn market_type_of(request: &OrderRequest) -> MarketType {
+    let futopt = match request {
+        OrderRequest::FutOpt(order) => order,
+        OrderRequest::FutOptCondition(condition_order) => &condition_order.order,
+        _ => return MarketType::Stock,
+    };
+
+    // Symbols the parser does not know are treated as futures
+    match futopt.contract() {
+        Ok(contract) if contract.is_option() => MarketType::Option,
+        _ => MarketType::Future,
+    }
+}
+
+/// Time priority stamp for a newly placed or repriced order
+fn next_priority() -> u64 {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+#[async_trait]
+impl Backend for PaperBackend {
+    fn events(&self) -> TradeEvents {
+        self.orders.events()
+    }
+
+    async fn login(&self, credentials: &LoginCredentials) -> Result<LoginSession> {
+        self.orders.login(credentials).await
+    }
+
+    async fn logout(&self) -> Result<()> {
+        self.orders.logout().await
+    }
+
+    async fn exchange_realtime_token(&self) -> Result<RealtimeToken> {
+        self.orders.exchange_realtime_token().await
+    }
+
+    async fn place_order(&self, account: &Account, order: &Order) -> Result<OrderResult> {
+        self.orders.ensure_logged_in()?;
+        self.check_cash(account, order)?;
+
+        let placed = self.orders.place_order(account, order).await?;
+        self.submit(account, placed, order.time_in_force).await
+    }
+
+    async fn place_condition_order(&self, _account: &Account, _condition_order: &ConditionOrder) -> Result<OrderResult> {
+        Err(Error::api("Condition orders are not supported in paper trading"))
+    }
+
+    async fn place_futopt_order(&self, account: &Account, order: &FutOptOrder) -> Result<OrderResult> {
+        self.multiplier(&OrderRequest::FutOpt(order.clone()))?;
+
+        let placed = self.orders.place_futopt_order(account, order).await?;
+        self.submit(account, placed, TimeInForce::Day).await
+    }
+
+    async fn place_futopt_condition_order(&self, _account: &Account, _condition_order: &FutOptConditionOrder) -> Result<OrderResult> {
+        Err(Error::api("Condition orders are not supported in paper trading"))
+    }
+
+    async fn modify_price(&self, account: &Account, order: &OrderResult, price: Price) -> Result<OrderResult> {
+        let modified = self.orders.modify_price(account, order, price).await?;
+
+        // A new price loses time priority and may trade at once
+        self.priorities()?.insert(modified.order_no.clone(), next_priority());
+        self.match_symbol(modified.request.symbol(), None)?;
+        self.orders.get_order_status(account, &modified.order_no).await
+    }
+
+    async fn modify_quantity(&self, account: &Account, order: &OrderResult, quantity: u32) -> Result<OrderResult> {
+        self.orders.modify_quantity(account, order, quantity).await
+    }
+
+    async fn cancel_order(&self, account: &Account, order_id: &str) -> Result<()> {
+        self.orders.cancel_order(account, order_id).await
+    }
+
+    async fn get_order_status(&self, account: &Account, order_id: &str) -> Result<OrderResult> {
+        self.orders.get_order_status(account, order_id).await
+    }
+
+    async fn get_order_results(&self, account: &Account) -> Result<Vec<OrderResult>> {
+        self.orders.get_order_results(account).await
+    }
+
+    async fn get_order_history(
+        &self,
+        account: &Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        status: Option<HistoryStatus>,
+    ) -> Result<Vec<OrderResult>> {
+        self.orders.get_order_history(account, from, to, status).await
+    }
+
+    async fn get_filled_history(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<FillRecord>> {
+        self.orders.get_filled_history(account, from, to).await
+    }
+
+    async fn get_realized_pnl(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<RealizedPnl>> {
+        self.orders.get_realized_pnl(account, from, to).await
+    }
+
+    async fn get_unrealized_pnl(&self, account: &Account) -> Result<Vec<UnrealizedPnl>> {
+        Ok(self.holdings(account)?.into_iter().map(UnrealizedPnl::from_position).collect())
+    }
+
+    async fn get_account_balance(&self, account: &Account) -> Result<Balance> {
+        self.orders.ensure_logged_in()?;
+        let account = self.orders.account(account)?;
+
+        let cash = self.cash(account)?;
+        let reserved = self.reserved_cash(account)?;
+        let today = trading_day(Utc::now());
+        let settlement = |days| Settlement { date: add_trading_days(today, days), amount: Decimal::ZERO };
+
+        Ok(Balance {
+            account_id: account.account_id.clone(),
+            currency: account.currency.clone(),
+            bank_balance: cash,
+            buying_power: cash - reserved,
+            t0: settlement(0),
+            t1: settlement(1),
+            t2: settlement(2),
+        })
+    }
+
+    async fn get_futopt_equity(&self, account: &Account) -> Result<FutOptEquity> {
+        let positions = self.get_futopt_positions(account).await?;
+        let account = self.orders.account(account)?;
+
+        let unrealized_pnl = positions.unrealized_pnl();
+        let equity = self.cash(account)? + unrealized_pnl;
+
+        Ok(FutOptEquity {
+            account_id: account.account_id.clone(),
+            currency: account.currency.clone(),
+            equity,
+            initial_margin: Decimal::ZERO,
+            maintenance_margin: Decimal::ZERO,
+            excess_margin: equity,
+            risk_indicator: None,
+            unrealized_pnl,
+        })
+    }
+
+    async fn get_futopt_positions(&self, account: &Account) -> Result<FutOptPositions> {
+        let single = self.holdings(account)?
+            .into_iter()
+            .filter(|position| !position.is_stock())
+            .collect();
+        Ok(FutOptPositions { single, combined: Vec::new() })
+    }
+
+    async fn get_margin_quota(&self, account: &Account, symbol: &str) -> Result<MarginQuota> {
+        self.orders.get_margin_quota(account, symbol).await
+    }
+
+    async fn get_positions(&self, account: &Account) -> Result<Vec<Position>> {
+        self.holdings(account)
+    }
+}
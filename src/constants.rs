@@ -1,5 +1,7 @@
 //! Constants used throughout the SDK, matching Python version
 
+use crate::decimal::Decimal;
+
 pub const AUTHENTICATION_TIMEOUT_MESSAGE: &str = "Authentication timeout";
 pub const CONNECT_EVENT: &str = "connect";
 pub const DISCONNECT_EVENT: &str = "disconnect";
@@ -20,4 +22,62 @@ pub const AUTH_TIMEOUT: u64 = 5;
 pub const PING_INTERVAL: u64 = 30;
 
 /// Maximum missed pongs before disconnect
-pub const MAX_MISSED_PONGS: u32 = 2;
\ No newline at end of file
+pub const MAX_MISSED_PONGS: u32 = 2;
+
+/// Default base URL of the trading API
+pub const DEFAULT_TRADE_BASE_URL: &str = "https://api.fubon.com";
+
+/// Trading API request timeout in seconds
+pub const TRADE_REQUEST_TIMEOUT: u64 = 30;
+
+/// Default number of order requests a batch keeps in flight
+pub const DEFAULT_ORDER_CONCURRENCY: usize = 5;
+
+/// Default maximum number of order requests per second
+pub const DEFAULT_ORDER_RATE_LIMIT: u32 = 10;
+
+/// TWD value of one price point of TAIFEX products, by monthly product code
+pub const FUTOPT_CONTRACT_MULTIPLIERS: [(&str, i64); 6] = [
+    ("TXF", 200),
+    ("MXF", 50),
+    ("TMF", 10),
+    ("TXO", 50),
+    ("EXF", 4000),
+    ("FXF", 1000),
+];
+
+/// Shares per board lot (張) on TWSE/TPEx
+pub const STOCK_LOT_SIZE: u64 = 1000;
+
+/// UTC offset of the Taiwan market in seconds, used to map timestamps to trading days
+pub const MARKET_UTC_OFFSET: i32 = 8 * 3600;
+
+/// Number of trading events buffered for each stream subscriber
+pub const TRADE_EVENT_CAPACITY: usize = 1024;
+
+/// Delay in seconds before reconnecting the trading event stream
+pub const TRADE_EVENT_RECONNECT_DELAY: u64 = 5;
+
+/// Seconds before token expiry at which the session is renewed ahead of a call
+pub const SESSION_RENEW_MARGIN: i64 = 60;
+
+/// Number of session events buffered for each subscriber
+pub const SESSION_EVENT_CAPACITY: usize = 64;
+
+/// Seconds before expiry at which the realtime market data token is refreshed
+pub const REALTIME_TOKEN_REFRESH_MARGIN: i64 = 300;
+
+/// Delay in seconds before retrying a failed realtime token refresh
+pub const REALTIME_TOKEN_RETRY_DELAY: u64 = 15;
+
+/// Standard stock commission rate before broker discounts (0.1425%)
+pub const STOCK_COMMISSION_RATE: Decimal = Decimal::new(1425, 6);
+
+/// Minimum stock commission per trade in TWD
+pub const STOCK_MIN_COMMISSION: Decimal = Decimal::new(20, 0);
+
+/// Securities transaction tax on stock sales (0.3%)
+pub const STOCK_TRANSACTION_TAX_RATE: Decimal = Decimal::new(3, 3);
+
+/// Securities transaction tax on day-trade stock sales (0.15%)
+pub const STOCK_DAY_TRADE_TAX_RATE: Decimal = Decimal::new(15, 4);
\ No newline at end of file
@@ -0,0 +1,249 @@
+//! TAIFEX futures and options symbols (商品代號).
+//!
+//! A symbol is a three character product code, the strike for options, a
+//! month code letter and the last digit of the year:
+//!
+//! - `TXFK4`: TAIEX futures (TXF), November 2024
+//! - `MXFA5`: mini TAIEX futures (MXF), January 2025
+//! - `TXO20000L4`: TAIEX call option (TXO) at 20000, December 2024
+//! - `TX120000X4`: first-week TAIEX put option (TX1) at 20000, December 2024
+//!
+//! Futures and calls use the letters `A` to `L` for January to December, and
+//! puts `M` to `X`.
+
+use std::fmt;
+use std::str::FromStr;
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, Error};
+use crate::constants::FUTOPT_CONTRACT_MULTIPLIERS;
+use crate::decimal::{Decimal, Price};
+use crate::types::*;
+
+/// Weekly TAIEX option products (週選擇權) and the week of the month they expire in;
+/// the third week is the monthly TXO
+const WEEKLY_OPTIONS: [(&str, u32); 4] = [("TX1", 1), ("TX2", 2), ("TX4", 4), ("TX5", 5)];
+
+/// Length of a product code
+const PRODUCT_LEN: usize = 3;
+
+/// Month code letter of a contract month, as used for futures and calls, or puts
+pub fn month_code(month: u32, call_put: Option<CallPut>) -> Option<char> {
+    if !(1..=12).contains(&month) {
+        return None;
+    }
+    let first = match call_put {
+        Some(CallPut::Put) => b'M',
+        _ => b'A',
+    };
+    Some((first + month as u8 - 1) as char)
+}
+
+/// Month and side of a month code letter; `None` for futures and calls
+/// (`A` to `L`), `Some(Put)` for puts (`M` to `X`)
+pub fn parse_month_code(code: char) -> Option<(u32, Option<CallPut>)> {
+    match code {
+        'A'..='L' => Some((code as u32 - 'A' as u32 + 1, None)),
+        'M'..='X' => Some((code as u32 - 'M' as u32 + 1, Some(CallPut::Put))),
+        _ => None,
+    }
+}
+
+/// Structured futures or options contract, parsed from or formatted to its symbol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractSpec {
+    /// Product code, e.g. `TXF`, `MXF`, `TXO` or the weekly `TX1`
+    pub product: String,
+    /// Contract year
+    pub year: i32,
+    /// Contract month, 1 to 12
+    pub month: u32,
+    /// Strike price, for options
+    pub strike: Option<Price>,
+    /// Call or put, for options
+    pub call_put: Option<CallPut>,
+}
+
+impl ContractSpec {
+    /// Futures contract of a product and month
+    pub fn future(product: impl Into<String>, year: i32, month: u32) -> Result<Self> {
+        Self::checked(Self {
+            product: product.into(),
+            year,
+            month,
+            strike: None,
+            call_put: None,
+        })
+    }
+
+    /// Option contract of a product, month, strike and side
+    pub fn option(product: impl Into<String>, year: i32, month: u32, strike: Price, call_put: CallPut) -> Result<Self> {
+        Self::checked(Self {
+            product: product.into(),
+            year,
+            month,
+            strike: Some(strike),
+            call_put: Some(call_put),
+        })
+    }
+
+    /// Parse a symbol, taking the year digit to mean the year ending in it
+    /// from five years before to four years after the current trading day
+    pub fn parse(symbol: &str) -> Result<Self> {
+        Self::parse_at(symbol, trading_day(Utc::now()))
+    }
+
+    /// Parse a symbol, resolving the year digit relative to `reference`
+    pub fn parse_at(symbol: &str, reference: NaiveDate) -> Result<Self> {
+        let invalid = |reason: &str| Error::invalid_symbol(symbol, reason);
+
+        if !symbol.is_ascii() || symbol.len() < PRODUCT_LEN + 2 {
+            return Err(invalid("expected a product code, month code and year digit"));
+        }
+
+        let (product, rest) = symbol.split_at(PRODUCT_LEN);
+        if !is_product_code(product) {
+            return Err(invalid("product code must be 3 uppercase letters or digits"));
+        }
+        let (body, year_digit) = rest.split_at(rest.len() - 1);
+        let (strike, code) = body.split_at(body.len() - 1);
+
+        let year_digit = year_digit
+            .parse::<i32>()
+            .map_err(|_| invalid("the last character must be the year digit"))?;
+        let code = code.chars().next().unwrap_or_default();
+        let (month, put) = parse_month_code(code)
+            .ok_or_else(|| invalid(&format!("unknown month code {:?}", code)))?;
+
+        // Options carry a strike; futures go straight to the month code
+        let (strike, call_put) = if strike.is_empty() {
+            if put.is_some() {
+                return Err(invalid(&format!("month code {:?} is for puts, but there is no strike", code)));
+            }
+            (None, None)
+        } else {
+            if !strike.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid(&format!("strike {:?} is not a number", strike)));
+            }
+            // Keeps the symbol round-tripping through Display, and rules out a zero strike
+            if strike.starts_with('0') {
+                return Err(invalid(&format!("strike {:?} starts with 0", strike)));
+            }
+            let strike = strike.parse::<Price>().map_err(|_| invalid("invalid strike"))?;
+            (Some(strike), Some(put.unwrap_or(CallPut::Call)))
+        };
+
+        Self::checked(Self {
+            product: product.to_string(),
+            year: resolve_year(year_digit, reference.year()),
+            month,
+            strike,
+            call_put,
+        })
+    }
+
+    /// Whether this is a futures contract
+    pub fn is_future(&self) -> bool {
+        self.strike.is_none()
+    }
+
+    /// Whether this is an option contract
+    pub fn is_option(&self) -> bool {
+        self.strike.is_some()
+    }
+
+    pub fn market_type(&self) -> FutOptMarketType {
+        if self.is_option() {
+            FutOptMarketType::Option
+        } else {
+            FutOptMarketType::Future
+        }
+    }
+
+    /// Week of the month a weekly option expires in, `None` for monthly contracts
+    pub fn week(&self) -> Option<u32> {
+        WEEKLY_OPTIONS
+            .iter()
+            .find(|(product, _)| *product == self.product)
+            .map(|(_, week)| *week)
+    }
+
+    /// Whether this is a weekly option
+    pub fn is_weekly(&self) -> bool {
+        self.week().is_some()
+    }
+
+    /// Monthly product the contract belongs to, `TXO` for the weekly TAIEX options
+    pub fn monthly_product(&self) -> &str {
+        if self.is_weekly() {
+            "TXO"
+        } else {
+            &self.product
+        }
+    }
+
+    /// TWD per price point of the built-in TAIFEX index products, by monthly product
+    pub fn multiplier(&self) -> Option<Decimal> {
+        FUTOPT_CONTRACT_MULTIPLIERS
+            .iter()
+            .find(|(product, _)| *product == self.monthly_product())
+            .map(|(_, multiplier)| Decimal::from(*multiplier))
+    }
+
+    /// Exchange symbol of the contract
+    pub fn symbol(&self) -> String {
+        self.to_string()
+    }
+
+    fn checked(spec: Self) -> Result<Self> {
+        if !is_product_code(&spec.product) {
+            return Err(Error::invalid_symbol(&spec.product, "product code must be 3 uppercase letters or digits"));
+        }
+        if !(1..=12).contains(&spec.month) {
+            return Err(Error::invalid_symbol(&spec.product, format!("month {} is not 1 to 12", spec.month)));
+        }
+        if let Some(strike) = spec.strike {
+            if !strike.is_positive() || strike != strike.floor() {
+                return Err(Error::invalid_symbol(&spec.product, format!("strike {} is not a positive whole number", strike)));
+            }
+        }
+        Ok(spec)
+    }
+}
+
+fn is_product_code(product: &str) -> bool {
+    product.len() == PRODUCT_LEN && product.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Year ending in `digit` from five years before to four years after `reference_year`
+fn resolve_year(digit: i32, reference_year: i32) -> i32 {
+    let earliest = reference_year - 5;
+    earliest + (digit - earliest).rem_euclid(10)
+}
+
+impl fmt::Display for ContractSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.product)?;
+        if let Some(strike) = self.strike {
+            write!(f, "{}", strike.normalize())?;
+        }
+        let code = month_code(self.month, self.call_put).unwrap_or('?');
+        write!(f, "{}{}", code, self.year.rem_euclid(10))
+    }
+}
+
+impl FromStr for ContractSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl FutOptOrder {
+    /// Contract the order is for, parsed from its symbol
+    pub fn contract(&self) -> Result<ContractSpec> {
+        ContractSpec::parse(&self.symbol)
+    }
+}
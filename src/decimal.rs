@@ -0,0 +1,255 @@
+//! Fixed-point decimal numbers for prices and money amounts.
+//!
+//! Values are exact in base ten, so tick prices and balances add up without
+//! binary rounding. Values are written to JSON as strings, e.g. `"500.5"`,
+//! like the Python `fubon_neo` SDK sends them, so they round-trip exactly.
+//! JSON numbers are accepted too, but are read through `f64` and keep only
+//! about 15 significant digits.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::RoundingStrategy;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Result, Error};
+
+/// Exact decimal number with up to 28 fractional digits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decimal(rust_decimal::Decimal);
+
+/// Price per share or contract
+pub type Price = Decimal;
+
+/// Money amount, such as a balance, notional value or profit
+pub type Amount = Decimal;
+
+impl Decimal {
+    pub const ZERO: Decimal = Decimal::new(0, 0);
+    pub const ONE: Decimal = Decimal::new(1, 0);
+
+    /// `mantissa` scaled down by `scale` decimal places, e.g. `Decimal::new(5005, 1)` for 500.5
+    pub const fn new(mantissa: i64, scale: u32) -> Self {
+        let magnitude = mantissa.unsigned_abs();
+        Self(rust_decimal::Decimal::from_parts(
+            magnitude as u32,
+            (magnitude >> 32) as u32,
+            0,
+            mantissa < 0,
+            scale,
+        ))
+    }
+
+    /// Decimal with the shortest representation of `value`, so that `0.1` becomes exactly 0.1.
+    /// Returns `None` for infinite and NaN values.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        rust_decimal::Decimal::from_str(&value.to_string())
+            .or_else(|_| rust_decimal::Decimal::from_scientific(&format!("{:e}", value)))
+            .ok()
+            .map(Self)
+    }
+
+    /// Nearest `f64`, for ratios and display
+    pub fn to_f64(self) -> f64 {
+        self.0.to_f64().unwrap_or(0.0)
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Largest whole number not above this one
+    pub fn floor(self) -> Self {
+        Self(self.0.floor())
+    }
+
+    /// Smallest whole number not below this one
+    pub fn ceil(self) -> Self {
+        Self(self.0.ceil())
+    }
+
+    /// Round to `dp` decimal places, half away from zero
+    pub fn round_dp(self, dp: u32) -> Self {
+        Self(self.0.round_dp_with_strategy(dp, RoundingStrategy::MidpointAwayFromZero))
+    }
+
+    /// Same value with trailing zeros removed, e.g. 500.50 to 500.5
+    pub fn normalize(self) -> Self {
+        Self(self.0.normalize())
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0.is_sign_negative() && !self.0.is_zero()
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0.is_sign_positive() && !self.0.is_zero()
+    }
+
+    /// Quotient, or `None` when dividing by zero
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.0.checked_div(other.0).map(Self)
+    }
+
+    /// Whether this is a whole multiple of `step`
+    pub fn is_multiple_of(self, step: Self) -> bool {
+        !step.is_zero() && (self.0 % step.0).is_zero()
+    }
+}
+
+/// A precision such as `{:.2}` rounds half away from zero rather than truncating
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(dp) => fmt::Display::fmt(&self.round_dp(dp as u32).0, f),
+            None => fmt::Display::fmt(&self.0, f),
+        }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        rust_decimal::Decimal::from_str(s)
+            .or_else(|_| rust_decimal::Decimal::from_scientific(s))
+            .map(Self)
+            .map_err(|_| Error::general(format!("Invalid decimal number: {:?}", s)))
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Decimal {
+                fn from(value: $int) -> Self {
+                    Self(rust_decimal::Decimal::from(value))
+                }
+            }
+
+            /// Value of a quantity at this price
+            impl Mul<$int> for Decimal {
+                type Output = Decimal;
+
+                fn mul(self, quantity: $int) -> Decimal {
+                    self * Decimal::from(quantity)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i32, i64, u32, u64);
+
+impl Add for Decimal {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Decimal {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl SubAssign for Decimal {
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0;
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(self.0 * other.0)
+    }
+}
+
+/// Panics when dividing by zero, see [`Decimal::checked_div`]
+impl Div for Decimal {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self(self.0 / other.0)
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Decimal> for Decimal {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
+struct DecimalVisitor;
+
+impl de::Visitor<'_> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a decimal number or numeric string")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> std::result::Result<Decimal, E> {
+        Ok(Decimal::from(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> std::result::Result<Decimal, E> {
+        Ok(Decimal::from(value))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> std::result::Result<Decimal, E> {
+        Decimal::from_f64(value).ok_or_else(|| E::custom(format!("invalid decimal number {}", value)))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> std::result::Result<Decimal, E> {
+        value.parse().map_err(E::custom)
+    }
+}
@@ -30,6 +30,36 @@ pub enum Error {
     #[error("Invalid mode for channel: {channel} not supported in {mode} mode")]
     InvalidModeForChannel { channel: String, mode: String },
     
+    #[error("Account {account_id} is a {account_type} account and cannot be used for {instrument}")]
+    AccountMismatch { account_id: String, account_type: String, instrument: String },
+    
+    #[error("{trading_type:?} order for {requested} shares of {symbol} exceeds the available quota of {available} shares")]
+    InsufficientQuota { symbol: String, trading_type: crate::types::TradingType, requested: u64, available: u64 },
+    
+    #[error("Order rejected by the {rule} rule: {reason}")]
+    RiskRejected { rule: crate::risk::RiskRule, reason: String },
+    
+    #[error("Invalid price {price} for {symbol}: {reason}")]
+    InvalidPrice { symbol: String, price: crate::decimal::Price, reason: String },
+    
+    #[error("Invalid contract symbol {symbol}: {reason}")]
+    InvalidSymbol { symbol: String, reason: String },
+    
+    #[error("Invalid order: {0}")]
+    InvalidOrder(#[from] crate::order::OrderError),
+    
+    #[error("Invalid order for the {session} session: {reason}")]
+    InvalidSession { session: crate::types::MarketSession, reason: String },
+    
+    #[error("Invalid {name}: {reason}")]
+    InvalidParameter { name: String, reason: String },
+    
+    #[error("Certificate error: {0}")]
+    Certificate(String),
+    
+    #[error("API error: {0}")]
+    Api(String),
+    
     #[error("Connection error: {0}")]
     Connection(String),
     
@@ -52,6 +82,58 @@ impl Error {
         Error::Authentication(msg.into())
     }
     
+    pub fn account_mismatch<T: Into<String>>(account: &crate::types::Account, instrument: T) -> Self {
+        Error::AccountMismatch {
+            account_id: account.account_id.clone(),
+            account_type: account.account_type.clone(),
+            instrument: instrument.into(),
+        }
+    }
+    
+    pub fn risk_rejected<T: Into<String>>(rule: crate::risk::RiskRule, reason: T) -> Self {
+        Error::RiskRejected {
+            rule,
+            reason: reason.into(),
+        }
+    }
+    
+    pub fn invalid_price<T: Into<String>>(symbol: &str, price: crate::decimal::Price, reason: T) -> Self {
+        Error::InvalidPrice {
+            symbol: symbol.to_string(),
+            price,
+            reason: reason.into(),
+        }
+    }
+    
+    pub fn invalid_symbol<T: Into<String>>(symbol: &str, reason: T) -> Self {
+        Error::InvalidSymbol {
+            symbol: symbol.to_string(),
+            reason: reason.into(),
+        }
+    }
+    
+    pub fn invalid_session<T: Into<String>>(session: crate::types::MarketSession, reason: T) -> Self {
+        Error::InvalidSession {
+            session,
+            reason: reason.into(),
+        }
+    }
+    
+    pub fn invalid_parameter<T: Into<String>>(name: &str, reason: T) -> Self {
+        Error::InvalidParameter {
+            name: name.to_string(),
+            reason: reason.into(),
+        }
+    }
+    
+    pub fn certificate<T: Into<String>>(msg: T) -> Self {
+        Error::Certificate(msg.into())
+    }
+    
+    pub fn api<T: Into<String>>(msg: T) -> Self {
+        Error::Api(msg.into())
+    }
+    
     pub fn connection<T: Into<String>>(msg: T) -> Self {
         Error::Connection(msg.into())
     }
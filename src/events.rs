@@ -0,0 +1,173 @@
+//! Trading events pushed by the backend: order acknowledgements, changes and fills
+
+use std::sync::{Arc, RwLock};
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::constants::TRADE_EVENT_CAPACITY;
+use crate::types::{FillRecord, OrderResult};
+
+/// What happened to an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderEventKind {
+    /// Order accepted by the broker
+    Placed,
+    /// Order rejected by the broker or exchange
+    Rejected,
+    /// Price changed
+    PriceModified,
+    /// Quantity reduced
+    QuantityModified,
+    /// Order cancelled
+    Cancelled,
+    /// Any other status change
+    StatusChanged,
+}
+
+/// Order acknowledgement or change (委託回報)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderEvent {
+    pub kind: OrderEventKind,
+    /// Order state after the event
+    pub order: OrderResult,
+    /// Time the event was reported
+    pub time: DateTime<Utc>,
+}
+
+impl OrderEvent {
+    pub fn new(kind: OrderEventKind, order: OrderResult) -> Self {
+        Self {
+            kind,
+            order,
+            time: Utc::now(),
+        }
+    }
+}
+
+/// Order execution (成交回報)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillEvent {
+    pub fill: FillRecord,
+    /// Order state after the fill
+    pub order: OrderResult,
+}
+
+/// Trading event, tagged as `{"event": ..., "data": ...}` on the wire
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum TradeEvent {
+    /// New order acknowledged or rejected
+    Order(OrderEvent),
+    /// Working order modified, cancelled or otherwise changed
+    OrderChanged(OrderEvent),
+    /// Order (partially) filled
+    Filled(FillEvent),
+    /// Event delivery problem, e.g. a dropped connection or missed events
+    Error(String),
+}
+
+impl TradeEvent {
+    /// Create an order event, routed to `Order` or `OrderChanged` by its kind
+    pub fn order(kind: OrderEventKind, order: OrderResult) -> Self {
+        let event = OrderEvent::new(kind, order);
+        match kind {
+            OrderEventKind::Placed | OrderEventKind::Rejected => TradeEvent::Order(event),
+            _ => TradeEvent::OrderChanged(event),
+        }
+    }
+}
+
+/// Callbacks for trading events, mirroring the Python SDK `on_order`,
+/// `on_order_changed` and `on_filled` setters.
+///
+/// Callbacks run on the thread that received the event and should return quickly.
+pub trait TradeEventHandler: Send + Sync {
+    fn on_order(&self, _event: &OrderEvent) {}
+
+    fn on_order_changed(&self, _event: &OrderEvent) {}
+
+    fn on_filled(&self, _event: &FillEvent) {}
+
+    fn on_error(&self, _message: &str) {}
+}
+
+struct Inner {
+    handlers: RwLock<Vec<Arc<dyn TradeEventHandler>>>,
+    sender: broadcast::Sender<TradeEvent>,
+}
+
+/// Fan-out point for trading events, delivering to handlers and stream subscribers.
+///
+/// Clones share the same handlers and subscribers.
+#[derive(Clone)]
+pub struct TradeEvents {
+    inner: Arc<Inner>,
+}
+
+impl TradeEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(TRADE_EVENT_CAPACITY);
+        Self {
+            inner: Arc::new(Inner {
+                handlers: RwLock::new(Vec::new()),
+                sender,
+            }),
+        }
+    }
+
+    /// Register callbacks for all future events
+    pub fn add_handler(&self, handler: Arc<dyn TradeEventHandler>) {
+        if let Ok(mut handlers) = self.inner.handlers.write() {
+            handlers.push(handler);
+        }
+    }
+
+    /// Receive all future events on a broadcast channel
+    pub fn subscribe(&self) -> broadcast::Receiver<TradeEvent> {
+        self.inner.sender.subscribe()
+    }
+
+    /// Receive all future events as an async stream.
+    ///
+    /// A consumer that falls more than the channel capacity behind receives a
+    /// [`TradeEvent::Error`] reporting how many events it missed.
+    pub fn stream(&self) -> BoxStream<'static, TradeEvent> {
+        stream::unfold(self.subscribe(), |mut receiver| async move {
+            match receiver.recv().await {
+                Ok(event) => Some((event, receiver)),
+                Err(RecvError::Lagged(missed)) => {
+                    let event = TradeEvent::Error(format!("Missed {} trade events", missed));
+                    Some((event, receiver))
+                }
+                Err(RecvError::Closed) => None,
+            }
+        })
+        .boxed()
+    }
+
+    /// Deliver an event to handlers and subscribers
+    pub fn emit(&self, event: TradeEvent) {
+        if let Ok(handlers) = self.inner.handlers.read() {
+            for handler in handlers.iter() {
+                match &event {
+                    TradeEvent::Order(order) => handler.on_order(order),
+                    TradeEvent::OrderChanged(order) => handler.on_order_changed(order),
+                    TradeEvent::Filled(fill) => handler.on_filled(fill),
+                    TradeEvent::Error(message) => handler.on_error(message),
+                }
+            }
+        }
+
+        // No subscribers is not an error
+        let _ = self.inner.sender.send(event);
+    }
+}
+
+impl Default for TradeEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
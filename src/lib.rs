@@ -3,15 +3,42 @@
 //! Rust implementation of Fubon Neo SDK for trading and market data.
 //! Compatible with Python 3.12+ fubon_neo package.
 
+pub mod backend;
 pub mod constants;
+pub mod contract;
+pub mod decimal;
 pub mod error;
+pub mod events;
 pub mod market_data;
+pub mod order;
+pub mod pnl;
+pub mod risk;
 pub mod sdk;
+pub mod session;
+pub mod throttle;
+pub mod tick;
+#[cfg(feature = "http")]
+pub mod trade;
 pub mod types;
 
+mod realtime;
+mod runtime;
+
 pub use error::{Error, Result};
-pub use sdk::{FubonSDK, CoreSDK};
+pub use contract::ContractSpec;
+pub use decimal::{Amount, Decimal, Price};
+pub use sdk::{FubonSDK, CoreSDK, AsyncCoreSDK};
 pub use market_data::{MarketData, RestClient, WebSocketClient, Mode};
+pub use backend::Backend;
+pub use events::{TradeEvent, TradeEventHandler, TradeEvents, OrderEvent, OrderEventKind, FillEvent};
+pub use order::{ConditionOrderBuilder, FutOptConditionOrderBuilder, FutOptOrderBuilder, OrderBuilder, OrderError};
+pub use pnl::{PnlReport, RealizedPnl, TradeCharges, UnrealizedPnl};
+pub use risk::{RiskGuard, RiskLimits, RiskRule};
+pub use session::{RealtimeToken, Session, SessionEvent, SessionEventHandler, SessionState};
+pub use throttle::ThrottleConfig;
+pub use tick::{PriceLimits, TickLadder};
+#[cfg(feature = "http")]
+pub use trade::{TradeClient, TradeConfig};
 pub use types::*;
 
 /// Library version, matching Python package version
@@ -1,5 +1,5 @@
 use clap::Parser;
-use r_fubon_neo::{FubonSDK, CoreSDK, Mode, LoginCredentials};
+use r_fubon_neo::{FubonSDK, CoreSDK, Mode, LoginCredentials, TradeConfig};
 use std::env;
 
 #[derive(Parser, Debug)]
@@ -21,6 +21,10 @@ struct Cli {
     #[arg(long)]
     cert_pass: Option<String>,
     
+    /// Trading API base URL (optional)
+    #[arg(long)]
+    trade_url: Option<String>,
+    
     /// Command to execute
     #[arg(value_enum)]
     command: Command,
@@ -48,6 +52,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     let cli = Cli::parse();
     
+    // Trading API endpoint from CLI args or environment variables
+    let mut trade_config = TradeConfig::new();
+    if let Some(url) = cli.trade_url.clone().or_else(|| env::var("FUBON_TRADE_URL").ok()) {
+        trade_config = trade_config.with_base_url(url);
+    }
+    
     match cli.command {
         Command::Version => {
             println!("r-fubon-neo version: {}", r_fubon_neo::VERSION);
@@ -76,7 +86,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     cert_pass,
                 };
                 
-                let mut sdk = FubonSDK::new();
+                let mut sdk = FubonSDK::with_trade_config(trade_config);
                 
                 // Login and get accounts
                 match sdk.login(credentials) {
@@ -134,24 +144,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     cert_pass,
                 };
                 
-                let mut sdk = FubonSDK::new();
+                let mut sdk = FubonSDK::with_trade_config(trade_config);
                 
                 // Login first
                 match sdk.login(credentials) {
                     Ok(accounts) => {
                         println!("Login successful! Found {} account(s)", accounts.len());
                         
-                        // Test basic SDK functionality
-                        match sdk.get_account_balance() {
-                            Ok(balance) => println!("Account balance: ${:.2}", balance),
-                            Err(e) => eprintln!("Error getting balance: {}", e),
-                        }
-                        
-                        match sdk.get_positions() {
-                            Ok(positions) => {
-                                println!("Positions: {:?}", positions);
+                        // Test basic SDK functionality on each account
+                        for account in &accounts {
+                            println!("Account {} ({}):", account.account_id, account.account_type);
+                            
+                            match sdk.get_account_balance(account) {
+                                Ok(balance) => println!(
+                                    "  Bank balance: ${:.2}, buying power: ${:.2}, pending settlement: ${:.2}",
+                                    balance.bank_balance, balance.buying_power, balance.pending_settlement()
+                                ),
+                                Err(e) => eprintln!("  Error getting balance: {}", e),
+                            }
+                            
+                            match sdk.get_positions(account) {
+                                Ok(positions) => {
+                                    println!("  Positions: {:?}", positions);
+                                }
+                                Err(e) => eprintln!("  Error getting positions: {}", e),
+                            }
+                            
+                            if account.is_futopt() {
+                                match sdk.get_futopt_equity(account) {
+                                    Ok(equity) => println!(
+                                        "  Equity: ${:.2}, initial margin: ${:.2}, maintenance margin: ${:.2}, excess: ${:.2}",
+                                        equity.equity, equity.initial_margin, equity.maintenance_margin, equity.excess_margin
+                                    ),
+                                    Err(e) => eprintln!("  Error getting equity: {}", e),
+                                }
                             }
-                            Err(e) => eprintln!("Error getting positions: {}", e),
                         }
                     }
                     Err(e) => eprintln!("Login failed: {}", e),
@@ -193,7 +220,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     cert_pass,
                 };
                 
-                let mut sdk = FubonSDK::new();
+                let mut sdk = FubonSDK::with_trade_config(trade_config);
                 
                 // Login first
                 match sdk.login(credentials) {
@@ -4,7 +4,12 @@ pub mod websocket;
 pub use rest::RestClient;
 pub use websocket::WebSocketClient;
 
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use tokio::sync::watch;
+
 use crate::Result;
+use crate::session::RealtimeToken;
 
 /// Market data mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,20 +27,71 @@ impl Mode {
     }
 }
 
+/// Realtime token shared by market data clients, so that a refreshed token
+/// also reaches clients that were handed out before the refresh
+#[derive(Debug, Clone)]
+pub(crate) struct SharedToken {
+    sender: Arc<watch::Sender<RealtimeToken>>,
+}
+
+impl SharedToken {
+    pub(crate) fn new(token: RealtimeToken) -> Self {
+        let (sender, _) = watch::channel(token);
+        Self { sender: Arc::new(sender) }
+    }
+    
+    pub(crate) fn get(&self) -> RealtimeToken {
+        self.sender.borrow().clone()
+    }
+    
+    pub(crate) fn set(&self, token: RealtimeToken) {
+        self.sender.send_replace(token);
+    }
+    
+    /// Watch for token replacements
+    pub(crate) fn subscribe(&self) -> watch::Receiver<RealtimeToken> {
+        self.sender.subscribe()
+    }
+}
+
 /// Market data wrapper combining REST and WebSocket clients
 pub struct MarketData {
     pub websocket_client: WebSocketClient,
     pub rest_client: RestClient,
+    token: SharedToken,
 }
 
 impl MarketData {
     pub fn new(sdk_token: String, mode: Mode) -> Result<Self> {
-        let websocket_client = WebSocketClient::new(mode, sdk_token.clone())?;
-        let rest_client = RestClient::new(sdk_token)?;
+        Self::with_token(RealtimeToken::new(sdk_token, None), mode)
+    }
+    
+    /// Create market data clients sharing a realtime token and its expiry
+    pub fn with_token(token: RealtimeToken, mode: Mode) -> Result<Self> {
+        let token = SharedToken::new(token);
         
         Ok(Self {
-            websocket_client,
-            rest_client,
+            websocket_client: WebSocketClient::with_shared_token(mode, token.clone()),
+            rest_client: RestClient::with_shared_token(token.clone()),
+            token,
         })
     }
+    
+    /// Time the realtime token expires, if known
+    pub fn token_expires_at(&self) -> Option<DateTime<Utc>> {
+        self.token.get().expires_at
+    }
+    
+    /// Replace the realtime token of the REST and WebSocket clients.
+    ///
+    /// REST clients, including those created earlier, send the new token from
+    /// their next request. A connected WebSocket re-authenticates on its open
+    /// connection, so its subscriptions stay in place.
+    pub fn update_token(&self, token: RealtimeToken) {
+        self.token.set(token);
+    }
+    
+    pub(crate) fn shared_token(&self) -> SharedToken {
+        self.token.clone()
+    }
 }
\ No newline at end of file
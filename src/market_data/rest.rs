@@ -2,6 +2,8 @@ use reqwest::Client;
 use serde_json::Value;
 use std::collections::HashMap;
 use crate::{Result, Error};
+use crate::market_data::SharedToken;
+use crate::session::RealtimeToken;
 
 /// Configuration for REST client
 #[derive(Debug, Clone)]
@@ -60,10 +62,34 @@ impl RestConfig {
     }
 }
 
+impl Default for RestConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Authentication headers for a request; a shared realtime token takes the
+/// place of the configured SDK token
+fn auth_headers(config: &RestConfig, token: Option<&SharedToken>) -> reqwest::header::HeaderMap {
+    let mut headers = reqwest::header::HeaderMap::new();
+    
+    if let Some(ref api_key) = config.api_key {
+        headers.insert("X-API-Key", api_key.parse().unwrap());
+    } else if let Some(ref bearer_token) = config.bearer_token {
+        headers.insert("Authorization", format!("Bearer {}", bearer_token).parse().unwrap());
+    } else if let Some(ref sdk_token) = config.sdk_token {
+        let sdk_token = token.map_or_else(|| sdk_token.clone(), |token| token.get().token);
+        headers.insert("X-SDK-Token", sdk_token.parse().unwrap());
+    }
+    
+    headers
+}
+
 /// Stock intraday data client
 pub struct Intraday {
     client: Client,
     config: RestConfig,
+    token: Option<SharedToken>,
 }
 
 impl Intraday {
@@ -72,20 +98,18 @@ impl Intraday {
         Ok(Self {
             client: Client::new(),
             config,
+            token: None,
         })
     }
     
+    pub(crate) fn with_shared_token(mut self, token: Option<SharedToken>) -> Self {
+        self.token = token;
+        self
+    }
+    
     pub async fn get_data(&self, symbol: &str) -> Result<Value> {
         let url = format!("{}/intraday/{}", self.config.base_url, symbol);
-        let mut headers = reqwest::header::HeaderMap::new();
-        
-        if let Some(ref api_key) = self.config.api_key {
-            headers.insert("X-API-Key", api_key.parse().unwrap());
-        } else if let Some(ref bearer_token) = self.config.bearer_token {
-            headers.insert("Authorization", format!("Bearer {}", bearer_token).parse().unwrap());
-        } else if let Some(ref sdk_token) = self.config.sdk_token {
-            headers.insert("X-SDK-Token", sdk_token.parse().unwrap());
-        }
+        let headers = auth_headers(&self.config, self.token.as_ref());
         
         let response = self.client
             .get(&url)
@@ -102,6 +126,7 @@ impl Intraday {
 pub struct Historical {
     client: Client,
     config: RestConfig,
+    token: Option<SharedToken>,
 }
 
 impl Historical {
@@ -110,24 +135,22 @@ impl Historical {
         Ok(Self {
             client: Client::new(),
             config,
+            token: None,
         })
     }
     
+    pub(crate) fn with_shared_token(mut self, token: Option<SharedToken>) -> Self {
+        self.token = token;
+        self
+    }
+    
     pub async fn get_data(&self, symbol: &str, from: &str, to: &str) -> Result<Value> {
         let url = format!("{}/historical/{}", self.config.base_url, symbol);
         let mut params = HashMap::new();
         params.insert("from", from);
         params.insert("to", to);
         
-        let mut headers = reqwest::header::HeaderMap::new();
-        
-        if let Some(ref api_key) = self.config.api_key {
-            headers.insert("X-API-Key", api_key.parse().unwrap());
-        } else if let Some(ref bearer_token) = self.config.bearer_token {
-            headers.insert("Authorization", format!("Bearer {}", bearer_token).parse().unwrap());
-        } else if let Some(ref sdk_token) = self.config.sdk_token {
-            headers.insert("X-SDK-Token", sdk_token.parse().unwrap());
-        }
+        let headers = auth_headers(&self.config, self.token.as_ref());
         
         let response = self.client
             .get(&url)
@@ -145,6 +168,7 @@ impl Historical {
 pub struct Snapshot {
     client: Client,
     config: RestConfig,
+    token: Option<SharedToken>,
 }
 
 impl Snapshot {
@@ -153,20 +177,18 @@ impl Snapshot {
         Ok(Self {
             client: Client::new(),
             config,
+            token: None,
         })
     }
     
+    pub(crate) fn with_shared_token(mut self, token: Option<SharedToken>) -> Self {
+        self.token = token;
+        self
+    }
+    
     pub async fn get_data(&self, symbol: &str) -> Result<Value> {
         let url = format!("{}/snapshot/{}", self.config.base_url, symbol);
-        let mut headers = reqwest::header::HeaderMap::new();
-        
-        if let Some(ref api_key) = self.config.api_key {
-            headers.insert("X-API-Key", api_key.parse().unwrap());
-        } else if let Some(ref bearer_token) = self.config.bearer_token {
-            headers.insert("Authorization", format!("Bearer {}", bearer_token).parse().unwrap());
-        } else if let Some(ref sdk_token) = self.config.sdk_token {
-            headers.insert("X-SDK-Token", sdk_token.parse().unwrap());
-        }
+        let headers = auth_headers(&self.config, self.token.as_ref());
         
         let response = self.client
             .get(&url)
@@ -182,42 +204,53 @@ impl Snapshot {
 /// Stock REST client
 pub struct RestStockClient {
     config: RestConfig,
+    token: Option<SharedToken>,
 }
 
 impl RestStockClient {
     pub fn new(config: RestConfig) -> Result<Self> {
         config.validate()?;
-        Ok(Self { config })
+        Ok(Self { config, token: None })
+    }
+    
+    pub(crate) fn with_shared_token(mut self, token: Option<SharedToken>) -> Self {
+        self.token = token;
+        self
     }
     
     pub fn intraday(&self) -> Result<Intraday> {
-        Intraday::new(self.config.clone())
+        Ok(Intraday::new(self.config.clone())?.with_shared_token(self.token.clone()))
     }
     
     pub fn historical(&self) -> Result<Historical> {
-        Historical::new(self.config.clone())
+        Ok(Historical::new(self.config.clone())?.with_shared_token(self.token.clone()))
     }
     
     pub fn snapshot(&self) -> Result<Snapshot> {
-        Snapshot::new(self.config.clone())
+        Ok(Snapshot::new(self.config.clone())?.with_shared_token(self.token.clone()))
     }
 }
 
 /// Main REST client
 pub struct RestClient {
-    sdk_token: String,
+    token: SharedToken,
 }
 
 impl RestClient {
     pub fn new(sdk_token: String) -> Result<Self> {
-        Ok(Self { sdk_token })
+        Ok(Self::with_shared_token(SharedToken::new(RealtimeToken::new(sdk_token, None))))
+    }
+    
+    pub(crate) fn with_shared_token(token: SharedToken) -> Self {
+        Self { token }
     }
     
+    /// Stock data clients, which keep using the latest realtime token
     pub fn stock(&self) -> Result<RestStockClient> {
         let config = RestConfig::new()
-            .with_sdk_token(self.sdk_token.clone())
+            .with_sdk_token(self.token.get().token)
             .with_base_url("https://api.fubon.com".to_string());
             
-        RestStockClient::new(config)
+        Ok(RestStockClient::new(config)?.with_shared_token(Some(self.token.clone())))
     }
 }
\ No newline at end of file
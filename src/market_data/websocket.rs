@@ -6,11 +6,13 @@ use tokio::time::{sleep, timeout};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use futures_util::{SinkExt, StreamExt};
 use serde_json::{json, Value};
+use tokio_util::sync::{CancellationToken, DropGuard};
 use url::Url;
 
 use crate::{Result, Error};
 use crate::constants::*;
-use crate::market_data::Mode;
+use crate::market_data::{Mode, SharedToken};
+use crate::session::RealtimeToken;
 
 /// Authentication state for WebSocket connection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -77,6 +79,12 @@ impl WebSocketConfig {
     }
 }
 
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// WebSocket client
 pub struct WebSocketClient {
     mode: Mode,
@@ -85,20 +93,32 @@ pub struct WebSocketClient {
     event_handlers: Arc<Mutex<Vec<Arc<dyn EventHandler>>>>,
     missed_pongs: Arc<Mutex<u32>>,
     sender: Option<mpsc::UnboundedSender<Message>>,
+    token: SharedToken,
+    /// Subscriptions to restore when connecting again
+    subscriptions: Mutex<Vec<HashMap<String, Value>>>,
+    /// Stops re-authenticating the current connection on token changes
+    token_watcher: Option<DropGuard>,
 }
 
 impl WebSocketClient {
     pub fn new(mode: Mode, sdk_token: String) -> Result<Self> {
-        let config = WebSocketConfig::new().with_sdk_token(sdk_token);
+        Ok(Self::with_shared_token(mode, SharedToken::new(RealtimeToken::new(sdk_token, None))))
+    }
+    
+    pub(crate) fn with_shared_token(mode: Mode, token: SharedToken) -> Self {
+        let config = WebSocketConfig::new().with_sdk_token(token.get().token);
         
-        Ok(Self {
+        Self {
             mode,
             config,
             auth_state: Arc::new(Mutex::new(AuthenticationState::Pending)),
             event_handlers: Arc::new(Mutex::new(Vec::new())),
             missed_pongs: Arc::new(Mutex::new(0)),
             sender: None,
-        })
+            token,
+            subscriptions: Mutex::new(Vec::new()),
+            token_watcher: None,
+        }
     }
     
     /// Add event handler
@@ -148,7 +168,13 @@ impl WebSocketClient {
             "data": params
         });
         
-        self.send_message(subscribe_msg).await
+        self.send_message(subscribe_msg).await?;
+        
+        let mut subscriptions = self.subscriptions.lock().await;
+        if !subscriptions.contains(&params) {
+            subscriptions.push(params);
+        }
+        Ok(())
     }
     
     /// Unsubscribe from channel
@@ -158,7 +184,30 @@ impl WebSocketClient {
             "data": params
         });
         
-        self.send_message(unsubscribe_msg).await
+        self.send_message(unsubscribe_msg).await?;
+        
+        // Forget every subscription the parameters match
+        self.subscriptions.lock().await.retain(|subscription| {
+            !params.iter().all(|(key, value)| subscription.get(key) == Some(value))
+        });
+        Ok(())
+    }
+    
+    /// Subscriptions made on this client, restored whenever it connects again
+    pub async fn active_subscriptions(&self) -> Vec<HashMap<String, Value>> {
+        self.subscriptions.lock().await.clone()
+    }
+    
+    /// Send the recorded subscriptions on a new connection
+    async fn resubscribe(&self) -> Result<()> {
+        let subscriptions = self.subscriptions.lock().await.clone();
+        for params in subscriptions {
+            self.send_message(json!({
+                "event": "subscribe",
+                "data": params
+            })).await?;
+        }
+        Ok(())
     }
     
     /// Get current subscriptions
@@ -198,13 +247,8 @@ impl WebSocketClient {
                     "token": bearer_token
                 }
             })
-        } else if let Some(ref sdk_token) = self.config.sdk_token {
-            json!({
-                "event": "auth",
-                "data": {
-                    "sdkToken": sdk_token
-                }
-            })
+        } else if self.config.sdk_token.is_some() {
+            sdk_auth_message(&self.token.get().token)
         } else {
             *self.auth_state.lock().await = AuthenticationState::Unauthenticated;
             return Err(Error::MissingCredentials);
@@ -274,6 +318,9 @@ impl WebSocketClient {
         // Start ping timer
         self.start_ping_timer().await;
         
+        // Re-authenticate this connection whenever the realtime token is replaced
+        self.token_watcher = self.watch_token().map(CancellationToken::drop_guard);
+        
         let auth_state = Arc::clone(&self.auth_state);
         let event_handlers = Arc::clone(&self.event_handlers);
         let missed_pongs = Arc::clone(&self.missed_pongs);
@@ -371,16 +418,54 @@ impl WebSocketClient {
         ).await;
         
         match auth_timeout {
-            Ok(result) => result,
+            Ok(result) => result?,
             Err(_) => {
                 *self.auth_state.lock().await = AuthenticationState::Unauthenticated;
-                Err(Error::AuthenticationTimeout)
+                return Err(Error::AuthenticationTimeout);
             }
         }
+        
+        // Restore subscriptions of an earlier connection
+        self.resubscribe().await
+    }
+    
+    /// Send a new auth message on the open connection each time the token changes
+    fn watch_token(&self) -> Option<CancellationToken> {
+        if self.config.api_key.is_some() || self.config.bearer_token.is_some() {
+            return None;
+        }
+        
+        let sender = self.sender.clone()?;
+        let auth_state = Arc::clone(&self.auth_state);
+        let mut changes = self.token.subscribe();
+        let cancel = CancellationToken::new();
+        let stop = cancel.clone();
+        
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = stop.cancelled() => break,
+                    changed = changes.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                }
+                
+                let text = sdk_auth_message(&changes.borrow_and_update().token).to_string();
+                if sender.send(Message::Text(text)).is_err() {
+                    break;
+                }
+                *auth_state.lock().await = AuthenticationState::Authenticating;
+            }
+        });
+        
+        Some(cancel)
     }
     
     /// Disconnect from WebSocket
     pub async fn disconnect(&mut self) {
+        self.token_watcher = None;
         if let Some(sender) = self.sender.take() {
             let _ = sender.send(Message::Close(None));
         }
@@ -392,4 +477,25 @@ impl WebSocketClient {
     pub async fn auth_state(&self) -> AuthenticationState {
         *self.auth_state.lock().await
     }
-}
\ No newline at end of file
+}
+
+/// Auth message for a realtime SDK token
+fn sdk_auth_message(sdk_token: &str) -> Value {
+    json!({
+        "event": "auth",
+        "data": {
+            "sdkToken": sdk_token
+        }
+    })
+}
+
+/// Channel and payload of a streaming market data message, e.g. `trades` or `books`
+pub(crate) fn market_data_message(text: &str) -> Option<(String, Value)> {
+    let mut msg = serde_json::from_str::<Value>(text).ok()?;
+    if msg.get("event")?.as_str()? != "data" {
+        return None;
+    }
+
+    let channel = msg.get("channel")?.as_str()?.to_string();
+    Some((channel, msg.get_mut("data")?.take()))
+}
@@ -0,0 +1,376 @@
+//! Validated construction of orders.
+//!
+//! Order structs can be written as literals, but the builders here check that
+//! the order type, prices, time in force and session fit together before an
+//! order is placed. `place_order` and friends run the same checks.
+
+use thiserror::Error;
+
+use crate::Result;
+use crate::decimal::Price;
+use crate::types::*;
+
+/// Combination of order fields that cannot be sent
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum OrderError {
+    #[error("symbol cannot be empty")]
+    EmptySymbol,
+
+    #[error("quantity must be greater than 0")]
+    ZeroQuantity,
+
+    #[error("{order_type} orders need a limit price")]
+    MissingPrice { order_type: &'static str },
+
+    #[error("{order_type} orders cannot have a limit price")]
+    UnexpectedPrice { order_type: &'static str },
+
+    #[error("{order_type} orders need a stop price")]
+    MissingStopPrice { order_type: &'static str },
+
+    #[error("{order_type} orders cannot have a stop price")]
+    UnexpectedStopPrice { order_type: &'static str },
+
+    #[error("{order_type} orders cannot use time in force {time_in_force}")]
+    TimeInForceNotAllowed { order_type: &'static str, time_in_force: TimeInForce },
+
+    #[error("condition symbol cannot be empty")]
+    EmptyConditionSymbol,
+
+    #[error("condition value must be greater than 0")]
+    NonPositiveConditionValue,
+
+    #[error("{condition_type} condition orders cannot send {order_type} orders")]
+    ConditionOrderTypeMismatch { condition_type: ConditionOrderType, order_type: &'static str },
+
+    #[error("future/option condition orders cannot send {order_type} orders")]
+    FutOptConditionOrderType { order_type: &'static str },
+}
+
+/// Which prices an order type needs: (limit price, stop price)
+fn required_prices(order_type: OrderType) -> (bool, bool) {
+    match order_type {
+        OrderType::Market => (false, false),
+        OrderType::Limit => (true, false),
+        OrderType::Stop => (false, true),
+        OrderType::StopLimit => (true, true),
+    }
+}
+
+/// Stock order type with the same price rules as a future/option order type
+fn stock_order_type(order_type: FutOptOrderType) -> OrderType {
+    match order_type {
+        FutOptOrderType::Market => OrderType::Market,
+        FutOptOrderType::Limit => OrderType::Limit,
+        FutOptOrderType::Stop => OrderType::Stop,
+        FutOptOrderType::StopLimit => OrderType::StopLimit,
+    }
+}
+
+/// Check the fields every order has against its order type
+fn check_fields(
+    symbol: &str,
+    quantity: u32,
+    order_type: OrderType,
+    price: Option<Price>,
+    stop_price: Option<Price>,
+) -> std::result::Result<(), OrderError> {
+    if symbol.is_empty() {
+        return Err(OrderError::EmptySymbol);
+    }
+
+    if quantity == 0 {
+        return Err(OrderError::ZeroQuantity);
+    }
+
+    let name = order_type.as_str();
+    let (needs_price, needs_stop) = required_prices(order_type);
+    match (needs_price, price.is_some()) {
+        (true, false) => return Err(OrderError::MissingPrice { order_type: name }),
+        (false, true) => return Err(OrderError::UnexpectedPrice { order_type: name }),
+        _ => {}
+    }
+    match (needs_stop, stop_price.is_some()) {
+        (true, false) => return Err(OrderError::MissingStopPrice { order_type: name }),
+        (false, true) => return Err(OrderError::UnexpectedStopPrice { order_type: name }),
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Check the trigger of a conditional order
+fn check_condition(condition: &Condition) -> std::result::Result<(), OrderError> {
+    if condition.symbol.is_empty() {
+        return Err(OrderError::EmptyConditionSymbol);
+    }
+
+    if !condition.value.is_positive() {
+        return Err(OrderError::NonPositiveConditionValue);
+    }
+
+    Ok(())
+}
+
+impl Order {
+    /// Start building a limit, ROD, cash order in the regular session
+    pub fn builder(symbol: impl Into<String>, action: BSAction, quantity: u32) -> OrderBuilder {
+        OrderBuilder::new(symbol, action, quantity)
+    }
+
+    /// Check that the order type, prices, time in force and session fit together.
+    ///
+    /// Market orders take no prices, limit orders a price, stop orders a stop
+    /// price and stop-limit orders both. Only market and limit orders can be
+    /// IOC or FOK, since stop orders wait for their trigger, and market orders
+    /// cannot be GTC. The session rules are those of [`validate_session`](Self::validate_session).
+    pub fn validate(&self) -> Result<()> {
+        check_fields(&self.symbol, self.quantity, self.order_type, self.price, self.stop_price)?;
+
+        let allowed = match self.order_type {
+            OrderType::Market => self.time_in_force != TimeInForce::Gtc,
+            OrderType::Limit => true,
+            OrderType::Stop | OrderType::StopLimit => matches!(self.time_in_force, TimeInForce::Day | TimeInForce::Gtc),
+        };
+        if !allowed {
+            return Err(OrderError::TimeInForceNotAllowed {
+                order_type: self.order_type.as_str(),
+                time_in_force: self.time_in_force,
+            }.into());
+        }
+
+        self.validate_session()
+    }
+}
+
+impl FutOptOrder {
+    /// Start building a future/option limit order
+    pub fn builder(symbol: impl Into<String>, action: BSAction, quantity: u32) -> FutOptOrderBuilder {
+        FutOptOrderBuilder::new(symbol, action, quantity)
+    }
+
+    /// Check that the prices fit the order type, as for stock orders
+    pub fn validate(&self) -> Result<()> {
+        check_fields(&self.symbol, self.quantity, stock_order_type(self.order_type), self.price, self.stop_price)?;
+        Ok(())
+    }
+}
+
+impl ConditionOrder {
+    /// Start building a conditional order sending `order` once `condition` is met
+    pub fn builder(order_type: ConditionOrderType, condition: Condition, order: Order) -> ConditionOrderBuilder {
+        ConditionOrderBuilder::new(order_type, condition, order)
+    }
+
+    /// Check the condition and the order it sends.
+    ///
+    /// The condition is the trigger, so the order is a market order for
+    /// [`Stop`](ConditionOrderType::Stop), a limit order for
+    /// [`StopLimit`](ConditionOrderType::StopLimit) and either for
+    /// [`Oco`](ConditionOrderType::Oco).
+    pub fn validate(&self) -> Result<()> {
+        check_condition(&self.condition)?;
+
+        let allowed = match self.order_type {
+            ConditionOrderType::Stop => self.order.order_type == OrderType::Market,
+            ConditionOrderType::StopLimit => self.order.order_type == OrderType::Limit,
+            ConditionOrderType::Oco => matches!(self.order.order_type, OrderType::Market | OrderType::Limit),
+        };
+        if !allowed {
+            return Err(OrderError::ConditionOrderTypeMismatch {
+                condition_type: self.order_type,
+                order_type: self.order.order_type.as_str(),
+            }.into());
+        }
+
+        self.order.validate()
+    }
+}
+
+impl FutOptConditionOrder {
+    /// Start building a future/option conditional order sending `order` once `condition` is met
+    pub fn builder(condition: Condition, order: FutOptOrder) -> FutOptConditionOrderBuilder {
+        FutOptConditionOrderBuilder::new(condition, order)
+    }
+
+    /// Check the condition and the order it sends.
+    ///
+    /// The condition is the trigger, so the order is a market or limit order.
+    pub fn validate(&self) -> Result<()> {
+        check_condition(&self.condition)?;
+
+        if !matches!(self.order.order_type, FutOptOrderType::Market | FutOptOrderType::Limit) {
+            return Err(OrderError::FutOptConditionOrderType {
+                order_type: stock_order_type(self.order.order_type).as_str(),
+            }.into());
+        }
+
+        self.order.validate()
+    }
+}
+
+/// Builder of a validated stock [`Order`]
+#[derive(Debug, Clone)]
+pub struct OrderBuilder {
+    order: Order,
+}
+
+impl OrderBuilder {
+    pub fn new(symbol: impl Into<String>, action: BSAction, quantity: u32) -> Self {
+        Self {
+            order: Order {
+                symbol: symbol.into(),
+                quantity,
+                price: None,
+                stop_price: None,
+                order_type: OrderType::Limit,
+                time_in_force: TimeInForce::Day,
+                action,
+                trading_type: TradingType::Normal,
+                session: MarketSession::Regular,
+            },
+        }
+    }
+
+    pub fn with_order_type(mut self, order_type: OrderType) -> Self {
+        self.order.order_type = order_type;
+        self
+    }
+
+    pub fn with_price(mut self, price: Price) -> Self {
+        self.order.price = Some(price);
+        self
+    }
+
+    pub fn with_stop_price(mut self, stop_price: Price) -> Self {
+        self.order.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn with_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.order.time_in_force = time_in_force;
+        self
+    }
+
+    pub fn with_trading_type(mut self, trading_type: TradingType) -> Self {
+        self.order.trading_type = trading_type;
+        self
+    }
+
+    pub fn with_session(mut self, session: MarketSession) -> Self {
+        self.order.session = session;
+        self
+    }
+
+    /// Validated order, see [`Order::validate`]
+    pub fn build(self) -> Result<Order> {
+        self.order.validate()?;
+        Ok(self.order)
+    }
+}
+
+/// Builder of a validated [`FutOptOrder`]
+#[derive(Debug, Clone)]
+pub struct FutOptOrderBuilder {
+    order: FutOptOrder,
+}
+
+impl FutOptOrderBuilder {
+    pub fn new(symbol: impl Into<String>, action: BSAction, quantity: u32) -> Self {
+        Self {
+            order: FutOptOrder {
+                symbol: symbol.into(),
+                quantity,
+                price: None,
+                stop_price: None,
+                order_type: FutOptOrderType::Limit,
+                action,
+            },
+        }
+    }
+
+    pub fn with_order_type(mut self, order_type: FutOptOrderType) -> Self {
+        self.order.order_type = order_type;
+        self
+    }
+
+    pub fn with_price(mut self, price: Price) -> Self {
+        self.order.price = Some(price);
+        self
+    }
+
+    pub fn with_stop_price(mut self, stop_price: Price) -> Self {
+        self.order.stop_price = Some(stop_price);
+        self
+    }
+
+    /// Validated order, see [`FutOptOrder::validate`]
+    pub fn build(self) -> Result<FutOptOrder> {
+        self.order.validate()?;
+        Ok(self.order)
+    }
+}
+
+/// Builder of a validated [`ConditionOrder`]
+#[derive(Debug, Clone)]
+pub struct ConditionOrderBuilder {
+    condition_order: ConditionOrder,
+}
+
+impl ConditionOrderBuilder {
+    pub fn new(order_type: ConditionOrderType, condition: Condition, order: Order) -> Self {
+        Self {
+            condition_order: ConditionOrder {
+                condition,
+                order,
+                order_type,
+            },
+        }
+    }
+
+    pub fn with_condition(mut self, condition: Condition) -> Self {
+        self.condition_order.condition = condition;
+        self
+    }
+
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.condition_order.order = order;
+        self
+    }
+
+    /// Validated conditional order, see [`ConditionOrder::validate`]
+    pub fn build(self) -> Result<ConditionOrder> {
+        self.condition_order.validate()?;
+        Ok(self.condition_order)
+    }
+}
+
+/// Builder of a validated [`FutOptConditionOrder`]
+#[derive(Debug, Clone)]
+pub struct FutOptConditionOrderBuilder {
+    condition_order: FutOptConditionOrder,
+}
+
+impl FutOptConditionOrderBuilder {
+    pub fn new(condition: Condition, order: FutOptOrder) -> Self {
+        Self {
+            condition_order: FutOptConditionOrder { condition, order },
+        }
+    }
+
+    pub fn with_condition(mut self, condition: Condition) -> Self {
+        self.condition_order.condition = condition;
+        self
+    }
+
+    pub fn with_order(mut self, order: FutOptOrder) -> Self {
+        self.condition_order.order = order;
+        self
+    }
+
+    /// Validated conditional order, see [`FutOptConditionOrder::validate`]
+    pub fn build(self) -> Result<FutOptConditionOrder> {
+        self.condition_order.validate()?;
+        Ok(self.condition_order)
+    }
+}
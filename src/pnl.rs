@@ -0,0 +1,202 @@
+//! Profit and loss reports with itemised fees and taxes
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    STOCK_COMMISSION_RATE, STOCK_DAY_TRADE_TAX_RATE, STOCK_MIN_COMMISSION, STOCK_TRANSACTION_TAX_RATE,
+};
+use crate::decimal::{Amount, Decimal, Price};
+use crate::types::{BSAction, MarketType, Position, TradingType};
+
+/// Fees and taxes charged on trades
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TradeCharges {
+    /// Broker commission (手續費)
+    pub commission: Amount,
+    /// Securities or futures transaction tax (證交稅/期交稅)
+    pub transaction_tax: Amount,
+}
+
+impl TradeCharges {
+    /// Standard charges on a stock trade worth `value`, before broker discounts.
+    ///
+    /// Both sides pay commission; only the sell side pays transaction tax, at
+    /// the reduced rate for day trades.
+    pub fn stock(action: BSAction, value: Amount, trading_type: TradingType) -> Self {
+        let commission = if value.is_positive() {
+            (value * STOCK_COMMISSION_RATE).floor().max(STOCK_MIN_COMMISSION)
+        } else {
+            Decimal::ZERO
+        };
+
+        let tax_rate = match (action, trading_type) {
+            (BSAction::Buy, _) => Decimal::ZERO,
+            (BSAction::Sell, TradingType::DayTrade) => STOCK_DAY_TRADE_TAX_RATE,
+            (BSAction::Sell, _) => STOCK_TRANSACTION_TAX_RATE,
+        };
+
+        Self {
+            commission,
+            transaction_tax: (value * tax_rate).floor(),
+        }
+    }
+
+    pub fn total(&self) -> Amount {
+        self.commission + self.transaction_tax
+    }
+
+    /// Share of these charges, e.g. the part attributable to a partial close
+    pub fn scale(&self, factor: Decimal) -> Self {
+        Self {
+            commission: self.commission * factor,
+            transaction_tax: self.transaction_tax * factor,
+        }
+    }
+}
+
+impl std::ops::Add for TradeCharges {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            commission: self.commission + other.commission,
+            transaction_tax: self.transaction_tax + other.transaction_tax,
+        }
+    }
+}
+
+impl std::ops::AddAssign for TradeCharges {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// Profit and loss realized on one symbol over a period (已實現損益)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealizedPnl {
+    pub symbol: String,
+    pub market_type: MarketType,
+    /// Quantity closed, in shares for stocks and contracts for futures/options
+    pub quantity: u64,
+    /// Amount paid for the closed quantity
+    pub cost: Amount,
+    /// Amount received for the closed quantity
+    pub proceeds: Amount,
+    /// Fees and taxes of the opening and closing trades
+    pub charges: TradeCharges,
+    /// Profit and loss before fees and taxes
+    pub gross_pnl: Amount,
+    /// Profit and loss after fees and taxes
+    pub net_pnl: Amount,
+}
+
+/// Profit and loss of an open position at the last price (未實現損益)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnrealizedPnl {
+    pub symbol: String,
+    pub market_type: MarketType,
+    /// Direction of the position (`Buy` for long, `Sell` for short)
+    pub action: BSAction,
+    /// Quantity held, in shares for stocks and contracts for futures/options
+    pub quantity: u64,
+    pub avg_cost: Price,
+    pub last_price: Price,
+    /// Value of the position at the last price
+    pub market_value: Amount,
+    /// Estimated fees and taxes to close the position
+    pub charges: TradeCharges,
+    /// Profit and loss before fees and taxes
+    pub gross_pnl: Amount,
+    /// Profit and loss after the estimated closing fees and taxes
+    pub net_pnl: Amount,
+}
+
+impl UnrealizedPnl {
+    /// Unrealized profit and loss of a position, with stock closing charges
+    /// estimated at standard rates. Futures/options charges are not estimated.
+    pub fn from_position(position: Position) -> Self {
+        let market_value = position.last_price * position.quantity;
+        let charges = if position.is_stock() {
+            let closing = match position.action {
+                BSAction::Buy => BSAction::Sell,
+                BSAction::Sell => BSAction::Buy,
+            };
+            TradeCharges::stock(closing, market_value, position.trading_type)
+        } else {
+            TradeCharges::default()
+        };
+
+        Self {
+            symbol: position.symbol,
+            market_type: position.market_type,
+            action: position.action,
+            quantity: position.quantity,
+            avg_cost: position.avg_cost,
+            last_price: position.last_price,
+            market_value,
+            charges,
+            gross_pnl: position.unrealized_pnl,
+            net_pnl: position.unrealized_pnl - charges.total(),
+        }
+    }
+}
+
+/// Profit and loss of an account, per symbol and in total
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlReport {
+    pub account_id: String,
+    pub currency: String,
+    /// First trading day of the realized period
+    pub from: NaiveDate,
+    /// Last trading day of the realized period
+    pub to: NaiveDate,
+    pub generated_at: DateTime<Utc>,
+    /// Realized profit and loss per symbol
+    pub realized: Vec<RealizedPnl>,
+    /// Unrealized profit and loss per open position
+    pub unrealized: Vec<UnrealizedPnl>,
+    /// Net realized profit and loss of all symbols
+    pub realized_pnl: Amount,
+    /// Net unrealized profit and loss of all positions
+    pub unrealized_pnl: Amount,
+    /// Fees and taxes paid on realized trades
+    pub realized_charges: TradeCharges,
+    /// Estimated fees and taxes to close all positions
+    pub unrealized_charges: TradeCharges,
+}
+
+impl PnlReport {
+    pub fn new(
+        account_id: String,
+        currency: String,
+        from: NaiveDate,
+        to: NaiveDate,
+        realized: Vec<RealizedPnl>,
+        unrealized: Vec<UnrealizedPnl>,
+    ) -> Self {
+        let realized_pnl = realized.iter().map(|pnl| pnl.net_pnl).sum();
+        let unrealized_pnl = unrealized.iter().map(|pnl| pnl.net_pnl).sum();
+        let realized_charges = realized.iter().fold(TradeCharges::default(), |total, pnl| total + pnl.charges);
+        let unrealized_charges = unrealized.iter().fold(TradeCharges::default(), |total, pnl| total + pnl.charges);
+
+        Self {
+            account_id,
+            currency,
+            from,
+            to,
+            generated_at: Utc::now(),
+            realized,
+            unrealized,
+            realized_pnl,
+            unrealized_pnl,
+            realized_charges,
+            unrealized_charges,
+        }
+    }
+
+    /// Net realized and unrealized profit and loss
+    pub fn total_pnl(&self) -> Amount {
+        self.realized_pnl + self.unrealized_pnl
+    }
+}
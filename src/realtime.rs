@@ -0,0 +1,76 @@
+//! Background refresh of the realtime market data token
+
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use tokio::runtime::Builder;
+use tokio_util::sync::CancellationToken;
+
+use crate::Result;
+use crate::backend::Backend;
+use crate::constants::{REALTIME_TOKEN_REFRESH_MARGIN, REALTIME_TOKEN_RETRY_DELAY};
+use crate::market_data::SharedToken;
+use crate::session::{RealtimeToken, Session, SessionEvent};
+
+/// Replace the realtime token ahead of each expiry until the returned token is cancelled.
+///
+/// Like the trading event listener, the refresher runs on its own thread and
+/// runtime so that it keeps going when the SDK is used from blocking code.
+/// Outcomes are reported as session events; a failed refresh is retried.
+pub(crate) fn spawn_token_refresher(
+    backend: Arc<dyn Backend>,
+    session: Arc<Session>,
+    token: SharedToken,
+) -> Result<CancellationToken> {
+    let cancel = CancellationToken::new();
+    let stop = cancel.clone();
+
+    std::thread::Builder::new()
+        .name("fubon-realtime-token".to_string())
+        .spawn(move || {
+            let runtime = match Builder::new_current_thread().enable_all().build() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    session.emit(SessionEvent::RealtimeTokenRefreshFailed(e.to_string()));
+                    return;
+                }
+            };
+
+            runtime.block_on(async move {
+                // A token without an expiry never needs replacing
+                while let Some(delay) = refresh_delay(&token.get()) {
+                    tokio::select! {
+                        _ = stop.cancelled() => break,
+                        _ = tokio::time::sleep(delay) => {}
+                    }
+
+                    match session.run(backend.as_ref(), || backend.exchange_realtime_token()).await {
+                        Ok(refreshed) => {
+                            let expires_at = refreshed.expires_at;
+                            token.set(refreshed);
+                            session.emit(SessionEvent::RealtimeTokenRefreshed { expires_at });
+                        }
+                        Err(e) => {
+                            session.emit(SessionEvent::RealtimeTokenRefreshFailed(e.to_string()));
+                            tokio::select! {
+                                _ = stop.cancelled() => break,
+                                _ = tokio::time::sleep(Duration::from_secs(REALTIME_TOKEN_RETRY_DELAY)) => {}
+                            }
+                        }
+                    }
+                }
+            });
+        })?;
+
+    Ok(cancel)
+}
+
+/// Time to wait before replacing a token: the refresh margin ahead of its
+/// expiry, or half of the remaining lifetime for short-lived tokens
+fn refresh_delay(token: &RealtimeToken) -> Option<Duration> {
+    let remaining = token.expires_at? - Utc::now();
+    let delay = (remaining - chrono::Duration::seconds(REALTIME_TOKEN_REFRESH_MARGIN)).max(remaining / 2);
+
+    // Past the expiry, wait a little so that a skewed clock cannot make the refresher spin
+    Some(delay.to_std().unwrap_or(Duration::from_secs(REALTIME_TOKEN_RETRY_DELAY)))
+}
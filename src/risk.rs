@@ -0,0 +1,405 @@
+//! Pre-trade risk checks run before orders are sent
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::{Result, Error};
+use crate::contract::ContractSpec;
+use crate::decimal::{Amount, Decimal, Price};
+use crate::market_data::websocket::{market_data_message, EventHandler, WebSocketEvent};
+use crate::types::*;
+
+/// Pre-trade rule an order can break
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RiskRule {
+    MaxOrderQuantity,
+    MaxOrderNotional,
+    MaxDailyNotional,
+    PositionLimit,
+    RestrictedSymbol,
+    PriceCollar,
+}
+
+impl fmt::Display for RiskRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RiskRule::MaxOrderQuantity => "max order quantity",
+            RiskRule::MaxOrderNotional => "max order notional",
+            RiskRule::MaxDailyNotional => "max daily notional",
+            RiskRule::PositionLimit => "position limit",
+            RiskRule::RestrictedSymbol => "restricted symbol",
+            RiskRule::PriceCollar => "price collar",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Limits checked before orders are sent; each is off until set.
+///
+/// Quantities are in shares for stocks and contracts for futures/options.
+/// Notional is in TWD: price times quantity for stocks, and price times
+/// quantity times the product's contract multiplier for futures/options.
+#[derive(Debug, Clone, Default)]
+pub struct RiskLimits {
+    /// Maximum quantity of one order
+    pub max_order_quantity: Option<u32>,
+    /// Maximum notional of one order
+    pub max_order_notional: Option<Amount>,
+    /// Maximum notional of all orders submitted in a trading day.
+    ///
+    /// This counts submitted rather than traded value: cancelled and unfilled
+    /// orders keep counting, and re-pricing an order counts the change in value
+    /// of its unfilled part.
+    pub max_daily_notional: Option<Amount>,
+    /// TWD per price point of futures/options products, by monthly product code
+    /// (e.g. `TXF`, `TXO`), in addition to the built-in TAIFEX index products
+    pub contract_multipliers: HashMap<String, Decimal>,
+    /// Maximum net position per symbol, long or short, once an order fills;
+    /// other working orders are not counted
+    pub position_limits: HashMap<String, u64>,
+    /// Symbols that must not be traded
+    pub restricted_symbols: HashSet<String>,
+    /// Maximum distance of a limit price from the last trade, as a fraction of it
+    pub price_collar: Option<Decimal>,
+}
+
+impl RiskLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_order_quantity(mut self, quantity: u32) -> Self {
+        self.max_order_quantity = Some(quantity);
+        self
+    }
+
+    pub fn with_max_order_notional(mut self, notional: Amount) -> Self {
+        self.max_order_notional = Some(notional);
+        self
+    }
+
+    pub fn with_max_daily_notional(mut self, notional: Amount) -> Self {
+        self.max_daily_notional = Some(notional);
+        self
+    }
+
+    /// Value futures/options of `product` at `multiplier` TWD per price point
+    pub fn with_contract_multiplier(mut self, product: &str, multiplier: Decimal) -> Self {
+        self.contract_multipliers.insert(product.to_string(), multiplier);
+        self
+    }
+
+    pub fn with_position_limit(mut self, symbol: &str, quantity: u64) -> Self {
+        self.position_limits.insert(symbol.to_string(), quantity);
+        self
+    }
+
+    pub fn with_restricted_symbols<I, S>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.restricted_symbols.extend(symbols.into_iter().map(Into::into));
+        self
+    }
+
+    /// Reject limit prices more than `fraction` (e.g. `0.05` for 5%) away from the last trade
+    pub fn with_price_collar(mut self, fraction: Decimal) -> Self {
+        self.price_collar = Some(fraction);
+        self
+    }
+
+    /// Whether an order must be valued, which needs a price for market orders
+    fn needs_notional(&self) -> bool {
+        self.max_order_notional.is_some() || self.max_daily_notional.is_some()
+    }
+
+    /// TWD per price point of a futures/options contract
+    fn contract_multiplier(&self, contract: &ContractSpec) -> Option<Decimal> {
+        self.contract_multipliers
+            .get(contract.monthly_product())
+            .copied()
+            .or_else(|| contract.multiplier())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TradeData {
+    symbol: String,
+    price: Price,
+}
+
+/// Applies [`RiskLimits`] to outgoing orders.
+///
+/// Last trade prices, used by the price collar and to value market orders,
+/// are recorded with [`record_trade`](Self::record_trade) or followed from the
+/// trades channel of a [`WebSocketClient`](crate::WebSocketClient) the guard
+/// is registered with as an event handler. Rules that need a last price reject
+/// orders for symbols without one, and notional limits reject futures/options
+/// of products without a contract multiplier. The daily notional counts orders
+/// accepted by the broker since the start of the market's trading day.
+///
+/// Reference prices (參考價) set with [`set_reference_price`](Self::set_reference_price)
+/// are used by the SDK to check stock limit prices against the daily price limits.
+#[derive(Debug)]
+pub struct RiskGuard {
+    limits: RiskLimits,
+    last_prices: Mutex<HashMap<String, Price>>,
+    reference_prices: Mutex<HashMap<String, Price>>,
+    /// Trading day and notional of the orders sent on it
+    daily_notional: Mutex<(NaiveDate, Amount)>,
+}
+
+impl RiskGuard {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self {
+            limits,
+            last_prices: Mutex::new(HashMap::new()),
+            reference_prices: Mutex::new(HashMap::new()),
+            daily_notional: Mutex::new((trading_day(Utc::now()), Decimal::ZERO)),
+        }
+    }
+
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Record the last trade price of a symbol
+    pub fn record_trade(&self, symbol: &str, price: Price) {
+        if let Ok(mut prices) = self.last_prices.lock() {
+            prices.insert(symbol.to_string(), price);
+        }
+    }
+
+    /// Last recorded trade price of a symbol
+    pub fn last_price(&self, symbol: &str) -> Option<Price> {
+        self.last_prices.lock().ok()?.get(symbol).copied()
+    }
+
+    /// Set the day's reference price of a symbol, usually the previous close;
+    /// replace it at the start of each trading day
+    pub fn set_reference_price(&self, symbol: &str, price: Price) {
+        if let Ok(mut prices) = self.reference_prices.lock() {
+            prices.insert(symbol.to_string(), price);
+        }
+    }
+
+    /// Reference price of a symbol the daily price limits are derived from
+    pub fn reference_price(&self, symbol: &str) -> Option<Price> {
+        self.reference_prices.lock().ok()?.get(symbol).copied()
+    }
+
+    /// Notional of the orders sent today
+    pub fn daily_notional(&self) -> Result<Amount> {
+        Ok(self.today()?.1)
+    }
+
+    /// Start today's notional over, as happens when the trading day changes
+    pub fn reset_daily_notional(&self) -> Result<()> {
+        self.today()?.1 = Decimal::ZERO;
+        Ok(())
+    }
+
+    /// Whether checking an order for `symbol` needs the account's current position
+    pub(crate) fn needs_position(&self, symbol: &str) -> bool {
+        self.limits.position_limits.contains_key(symbol)
+    }
+
+    /// Check an order against every rule and reserve its notional for today.
+    ///
+    /// `position` is the signed net position in the order's symbol, required
+    /// when the symbol has a position limit. Returns the reserved notional,
+    /// to be given back with [`release`](Self::release) if the order is not sent.
+    pub(crate) fn check(&self, request: &OrderRequest, position: Option<i64>) -> Result<Amount> {
+        let notional = self.check_order(request)?;
+        self.check_position(request, position)?;
+        self.reserve(notional)?;
+
+        Ok(notional)
+    }
+
+    /// Check a working order re-priced to `price` and reserve the rise in
+    /// notional of its unfilled part for today.
+    ///
+    /// Returns the change in notional. A rise is reserved at once, to be given
+    /// back with [`release`](Self::release) if the change is not accepted; a
+    /// fall is only given back by the caller once the change is accepted.
+    pub(crate) fn check_reprice(&self, current: &OrderResult, price: Price) -> Result<Amount> {
+        let mut modified = current.request.clone();
+        modified.set_price(price);
+        self.check_order(&modified)?;
+
+        if !self.limits.needs_notional() {
+            return Ok(Decimal::ZERO);
+        }
+
+        let previous = match current.request.price() {
+            Some(previous) => previous,
+            None => self.require_last_price(current.request.symbol(), RiskRule::MaxDailyNotional)?,
+        };
+        let multiplier = self.multiplier(&modified, RiskRule::MaxDailyNotional)?;
+        let change = (price - previous) * multiplier * current.remaining_quantity;
+        self.reserve(change.max(Decimal::ZERO))?;
+
+        Ok(change)
+    }
+
+    /// Rules on the order itself; returns its notional, zero when no notional limit is set
+    fn check_order(&self, request: &OrderRequest) -> Result<Amount> {
+        let limits = &self.limits;
+        let symbol = request.symbol();
+        let quantity = request.quantity();
+
+        if limits.restricted_symbols.contains(symbol) {
+            return Err(Error::risk_rejected(
+                RiskRule::RestrictedSymbol,
+                format!("{} is restricted", symbol),
+            ));
+        }
+
+        if let Some(max) = limits.max_order_quantity.filter(|max| quantity > *max) {
+            return Err(Error::risk_rejected(
+                RiskRule::MaxOrderQuantity,
+                format!("quantity {} exceeds {}", quantity, max),
+            ));
+        }
+
+        let limit_price = request.price().filter(|_| !request.is_market());
+        if let Some(collar) = limits.price_collar {
+            if let Some(price) = limit_price {
+                let last = self.require_last_price(symbol, RiskRule::PriceCollar)?;
+                if (price - last).abs() > last * collar {
+                    return Err(Error::risk_rejected(
+                        RiskRule::PriceCollar,
+                        format!("price {} is more than {}% away from the last trade {}", price, (collar * Decimal::from(100)).normalize(), last),
+                    ));
+                }
+            }
+        }
+
+        let notional = if limits.needs_notional() {
+            let rule = if limits.max_order_notional.is_some() { RiskRule::MaxOrderNotional } else { RiskRule::MaxDailyNotional };
+            let price = match limit_price {
+                Some(price) => price,
+                None => self.require_last_price(symbol, rule)?,
+            };
+            price * self.multiplier(request, rule)? * quantity
+        } else {
+            Decimal::ZERO
+        };
+
+        if let Some(max) = limits.max_order_notional.filter(|max| notional > *max) {
+            return Err(Error::risk_rejected(
+                RiskRule::MaxOrderNotional,
+                format!("notional {:.2} exceeds {:.2}", notional, max),
+            ));
+        }
+
+        Ok(notional)
+    }
+
+    fn check_position(&self, request: &OrderRequest, position: Option<i64>) -> Result<()> {
+        let symbol = request.symbol();
+        let Some(max) = self.limits.position_limits.get(symbol) else {
+            return Ok(());
+        };
+
+        let current = position.unwrap_or(0);
+        let quantity = request.quantity() as i64;
+        let change = match request.action() {
+            BSAction::Buy => quantity,
+            BSAction::Sell => -quantity,
+        };
+        let resulting = current + change;
+
+        // Orders that reduce an oversized position are let through
+        if resulting.unsigned_abs() > *max && resulting.abs() > current.abs() {
+            return Err(Error::risk_rejected(
+                RiskRule::PositionLimit,
+                format!("position in {} would reach {}, beyond {}", symbol, resulting, max),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Add `notional` to today's total if it stays within the daily limit
+    fn reserve(&self, notional: Amount) -> Result<()> {
+        let mut today = self.today()?;
+        if let Some(max) = self.limits.max_daily_notional {
+            if today.1 + notional > max {
+                return Err(Error::risk_rejected(
+                    RiskRule::MaxDailyNotional,
+                    format!("notional {:.2} would bring today's total to {:.2}, beyond {:.2}", notional, today.1 + notional, max),
+                ));
+            }
+        }
+        today.1 += notional;
+
+        Ok(())
+    }
+
+    /// TWD per price point of an order: 1 for stocks, the contract multiplier for futures/options
+    fn multiplier(&self, request: &OrderRequest, rule: RiskRule) -> Result<Decimal> {
+        if !request.is_futopt() {
+            return Ok(Decimal::ONE);
+        }
+
+        let symbol = request.symbol();
+        let contract = ContractSpec::parse(symbol)
+            .map_err(|_| Error::risk_rejected(rule, format!("cannot value {}: not a futures/options symbol", symbol)))?;
+        self.limits
+            .contract_multiplier(&contract)
+            .ok_or_else(|| Error::risk_rejected(rule, format!("no contract multiplier for {}", contract.monthly_product())))
+    }
+
+    /// Give back notional reserved for an order that was not accepted
+    pub(crate) fn release(&self, notional: Amount) {
+        if let Ok(mut today) = self.today() {
+            today.1 = (today.1 - notional).max(Decimal::ZERO);
+        }
+    }
+
+    fn require_last_price(&self, symbol: &str, rule: RiskRule) -> Result<Price> {
+        self.last_price(symbol)
+            .ok_or_else(|| Error::risk_rejected(rule, format!("no last trade price for {}", symbol)))
+    }
+
+    /// Today's notional, starting over on a new trading day
+    fn today(&self) -> Result<MutexGuard<'_, (NaiveDate, Amount)>> {
+        let mut daily = self.daily_notional
+            .lock()
+            .map_err(|_| Error::general("Risk guard lock poisoned"))?;
+
+        let today = trading_day(Utc::now());
+        if daily.0 != today {
+            *daily = (today, Decimal::ZERO);
+        }
+        Ok(daily)
+    }
+}
+
+impl Default for RiskGuard {
+    fn default() -> Self {
+        Self::new(RiskLimits::new())
+    }
+}
+
+impl EventHandler for RiskGuard {
+    fn handle_event(&self, event: WebSocketEvent) {
+        let WebSocketEvent::Message(text) = event else {
+            return;
+        };
+
+        if let Some((channel, data)) = market_data_message(&text) {
+            if channel == "trades" {
+                if let Ok(trade) = serde_json::from_value::<TradeData>(data) {
+                    self.record_trade(&trade.symbol, trade.price);
+                }
+            }
+        }
+    }
+}
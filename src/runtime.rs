@@ -0,0 +1,39 @@
+//! Helpers for driving async network calls from the synchronous SDK interface
+
+use std::future::Future;
+use tokio::runtime::{Builder, Handle, RuntimeFlavor};
+
+use crate::{Result, Error};
+
+/// Run a future to completion from synchronous code.
+///
+/// Inside a multi-threaded tokio runtime the current worker is handed over with
+/// `block_in_place`. A current-thread runtime cannot be blocked, so the future is
+/// driven on a scoped helper thread instead. Outside of tokio a private
+/// current-thread runtime is used.
+pub(crate) fn block_on<F, T>(future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>> + Send,
+    T: Send,
+{
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(future))
+        }
+        Ok(_) => std::thread::scope(|scope| {
+            scope
+                .spawn(|| run_local(future))
+                .join()
+                .map_err(|_| Error::general("Blocking SDK call panicked"))?
+        }),
+        Err(_) => run_local(future),
+    }
+}
+
+fn run_local<F, T>(future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let runtime = Builder::new_current_thread().enable_all().build()?;
+    runtime.block_on(future)
+}
\ No newline at end of file
@@ -1,76 +1,455 @@
+use std::future::Future;
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use tokio_util::sync::DropGuard;
+
 use crate::{Result, Error};
+use crate::backend::{default_backend, Backend};
+use crate::decimal::{Amount, Price};
+use crate::events::{TradeEvent, TradeEventHandler, TradeEvents};
+#[cfg(feature = "http")]
+use crate::backend::HttpBackend;
 use crate::market_data::{MarketData, Mode};
+use crate::pnl::{PnlReport, RealizedPnl, UnrealizedPnl};
+use crate::realtime::spawn_token_refresher;
+use crate::risk::{RiskGuard, RiskLimits};
+use crate::runtime::block_on;
+use crate::session::{RealtimeToken, Session, SessionEvent};
+use crate::throttle::{OrderThrottle, ThrottleConfig};
+use crate::tick;
+#[cfg(feature = "http")]
+use crate::trade::TradeConfig;
 use crate::types::*;
 
-/// Core SDK trait defining the main SDK interface
+/// Core SDK trait defining the main SDK interface.
+///
+/// This is the blocking interface. It is implemented for every [`AsyncCoreSDK`],
+/// driving the async call to completion on the calling thread.
 pub trait CoreSDK {
     /// Login and get account list
     fn login(&mut self, credentials: LoginCredentials) -> Result<Vec<Account>>;
     
+    /// End the session and forget the stored credentials
+    fn logout(&mut self) -> Result<()>;
+    
     /// Exchange realtime token for market data access
     fn exchange_realtime_token(&self) -> Result<String>;
     
     /// Place an order
-    fn place_order(&self, order: &Order) -> Result<String>;
+    fn place_order(&self, account: &Account, order: &Order) -> Result<OrderResult>;
+    
+    /// Place several orders, returning one result per order in input order
+    fn place_orders(&self, account: &Account, orders: &[Order]) -> Result<Vec<Result<OrderResult>>>;
+    
+    /// Place a conditional order
+    fn place_condition_order(&self, account: &Account, condition_order: &ConditionOrder) -> Result<OrderResult>;
+    
+    /// Place a future/option order
+    fn place_futopt_order(&self, account: &Account, order: &FutOptOrder) -> Result<OrderResult>;
+    
+    /// Place a future/option conditional order
+    fn place_futopt_condition_order(&self, account: &Account, condition_order: &FutOptConditionOrder) -> Result<OrderResult>;
+    
+    /// Change the price of a working order (改價)
+    fn modify_price(&self, account: &Account, order: OrderRef<'_>, price: Price) -> Result<OrderResult>;
+    
+    /// Reduce the total quantity of a working order (改量)
+    fn modify_quantity(&self, account: &Account, order: OrderRef<'_>, quantity: u32) -> Result<OrderResult>;
+    
+    /// Cancel an order
+    fn cancel_order(&self, account: &Account, order_id: &str) -> Result<()>;
+    
+    /// Cancel several orders, returning one result per order in input order
+    fn cancel_orders(&self, account: &Account, order_ids: &[&str]) -> Result<Vec<Result<()>>>;
+    
+    /// Get order status
+    fn get_order_status(&self, account: &Account, order_id: &str) -> Result<OrderResult>;
+    
+    /// Get today's orders (當日委託)
+    fn get_order_results(&self, account: &Account) -> Result<Vec<OrderResult>>;
+    
+    /// Get orders placed between two trading days (inclusive), optionally filtered by status
+    fn get_order_history(
+        &self,
+        account: &Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        status: Option<HistoryStatus>,
+    ) -> Result<Vec<OrderResult>>;
+    
+    /// Get fills between two trading days (inclusive)
+    fn get_filled_history(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<FillRecord>>;
+    
+    /// Get realized profit and loss per symbol between two trading days (inclusive)
+    fn get_realized_pnl(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<RealizedPnl>>;
+    
+    /// Get unrealized profit and loss of the open positions
+    fn get_unrealized_pnl(&self, account: &Account) -> Result<Vec<UnrealizedPnl>>;
+    
+    /// Get realized and unrealized profit and loss with account totals
+    fn get_pnl_report(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<PnlReport>;
+    
+    /// Get bank balance, buying power and T to T+2 settlements (交割款)
+    fn get_account_balance(&self, account: &Account) -> Result<Balance>;
+    
+    /// Get the equity and margin of a futures/options account (權益數/保證金)
+    fn get_futopt_equity(&self, account: &Account) -> Result<FutOptEquity>;
+    
+    /// Get futures/options positions, with combined (spread) positions separated
+    fn get_futopt_positions(&self, account: &Account) -> Result<FutOptPositions>;
+    
+    /// Get the margin and short sale quota of a symbol (融資融券額度)
+    fn get_margin_quota(&self, account: &Account, symbol: &str) -> Result<MarginQuota>;
+    
+    /// Get stock inventories and future/option positions
+    fn get_positions(&self, account: &Account) -> Result<Vec<Position>>;
+}
+
+/// Async SDK interface for use from tokio tasks.
+///
+/// Trading and query methods act on the account passed in, which must be one
+/// of the accounts returned by `login`. Stock orders need a stock account and
+/// future/option orders a futures account, otherwise
+/// [`Error::AccountMismatch`] is returned.
+#[async_trait]
+pub trait AsyncCoreSDK: Send + Sync {
+    /// Login and get account list
+    async fn login(&mut self, credentials: LoginCredentials) -> Result<Vec<Account>>;
+    
+    /// End the session and forget the stored credentials
+    async fn logout(&mut self) -> Result<()>;
+    
+    /// Exchange realtime token for market data access
+    async fn exchange_realtime_token(&self) -> Result<String>;
+    
+    /// Place an order
+    async fn place_order(&self, account: &Account, order: &Order) -> Result<OrderResult>;
+    
+    /// Place several orders with bounded concurrency.
+    ///
+    /// The outer error is returned when the batch cannot be submitted at all;
+    /// otherwise each order gets its own result, in input order, so a rejected
+    /// order does not hide the outcome of the others.
+    async fn place_orders(&self, account: &Account, orders: &[Order]) -> Result<Vec<Result<OrderResult>>>;
     
     /// Place a conditional order
-    fn place_condition_order(&self, condition_order: &ConditionOrder) -> Result<String>;
+    async fn place_condition_order(&self, account: &Account, condition_order: &ConditionOrder) -> Result<OrderResult>;
     
     /// Place a future/option order
-    fn place_futopt_order(&self, order: &FutOptOrder) -> Result<String>;
+    async fn place_futopt_order(&self, account: &Account, order: &FutOptOrder) -> Result<OrderResult>;
     
     /// Place a future/option conditional order
-    fn place_futopt_condition_order(&self, condition_order: &FutOptConditionOrder) -> Result<String>;
+    async fn place_futopt_condition_order(&self, account: &Account, condition_order: &FutOptConditionOrder) -> Result<OrderResult>;
+    
+    /// Change the price of a working order (改價)
+    async fn modify_price(&self, account: &Account, order: OrderRef<'_>, price: Price) -> Result<OrderResult>;
+    
+    /// Reduce the total quantity of a working order (改量).
+    ///
+    /// `quantity` is the new total order quantity; it must be below the current
+    /// quantity and not below what has already been filled.
+    async fn modify_quantity(&self, account: &Account, order: OrderRef<'_>, quantity: u32) -> Result<OrderResult>;
     
     /// Cancel an order
-    fn cancel_order(&self, order_id: &str) -> Result<()>;
+    async fn cancel_order(&self, account: &Account, order_id: &str) -> Result<()>;
+    
+    /// Cancel several orders with bounded concurrency, one result per order
+    async fn cancel_orders(&self, account: &Account, order_ids: &[&str]) -> Result<Vec<Result<()>>>;
     
     /// Get order status
-    fn get_order_status(&self, order_id: &str) -> Result<String>;
+    async fn get_order_status(&self, account: &Account, order_id: &str) -> Result<OrderResult>;
+    
+    /// Get today's orders (當日委託)
+    async fn get_order_results(&self, account: &Account) -> Result<Vec<OrderResult>>;
+    
+    /// Get orders placed between two trading days (inclusive), optionally filtered by status
+    async fn get_order_history(
+        &self,
+        account: &Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        status: Option<HistoryStatus>,
+    ) -> Result<Vec<OrderResult>>;
+    
+    /// Get fills between two trading days (inclusive)
+    async fn get_filled_history(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<FillRecord>>;
+    
+    /// Get realized profit and loss per symbol between two trading days (inclusive)
+    async fn get_realized_pnl(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<RealizedPnl>>;
+    
+    /// Get unrealized profit and loss of the open positions
+    async fn get_unrealized_pnl(&self, account: &Account) -> Result<Vec<UnrealizedPnl>>;
+    
+    /// Get realized and unrealized profit and loss with account totals
+    async fn get_pnl_report(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<PnlReport>;
     
-    /// Get account balance
-    fn get_account_balance(&self) -> Result<f64>;
+    /// Get bank balance, buying power and T to T+2 settlements (交割款)
+    async fn get_account_balance(&self, account: &Account) -> Result<Balance>;
     
-    /// Get positions
-    fn get_positions(&self) -> Result<Vec<String>>;
+    /// Get the equity and margin of a futures/options account (權益數/保證金)
+    async fn get_futopt_equity(&self, account: &Account) -> Result<FutOptEquity>;
+    
+    /// Get futures/options positions, with combined (spread) positions separated
+    async fn get_futopt_positions(&self, account: &Account) -> Result<FutOptPositions>;
+    
+    /// Get the margin and short sale quota of a symbol (融資融券額度)
+    async fn get_margin_quota(&self, account: &Account, symbol: &str) -> Result<MarginQuota>;
+    
+    /// Get stock inventories and future/option positions
+    async fn get_positions(&self, account: &Account) -> Result<Vec<Position>>;
+}
+
+/// Blocking adapter over any async SDK implementation
+impl<T: AsyncCoreSDK> CoreSDK for T {
+    fn login(&mut self, credentials: LoginCredentials) -> Result<Vec<Account>> {
+        block_on(AsyncCoreSDK::login(self, credentials))
+    }
+    
+    fn logout(&mut self) -> Result<()> {
+        block_on(AsyncCoreSDK::logout(self))
+    }
+    
+    fn exchange_realtime_token(&self) -> Result<String> {
+        block_on(AsyncCoreSDK::exchange_realtime_token(self))
+    }
+    
+    fn place_order(&self, account: &Account, order: &Order) -> Result<OrderResult> {
+        block_on(AsyncCoreSDK::place_order(self, account, order))
+    }
+    
+    fn place_orders(&self, account: &Account, orders: &[Order]) -> Result<Vec<Result<OrderResult>>> {
+        block_on(AsyncCoreSDK::place_orders(self, account, orders))
+    }
+    
+    fn place_condition_order(&self, account: &Account, condition_order: &ConditionOrder) -> Result<OrderResult> {
+        block_on(AsyncCoreSDK::place_condition_order(self, account, condition_order))
+    }
+    
+    fn place_futopt_order(&self, account: &Account, order: &FutOptOrder) -> Result<OrderResult> {
+        block_on(AsyncCoreSDK::place_futopt_order(self, account, order))
+    }
+    
+    fn place_futopt_condition_order(&self, account: &Account, condition_order: &FutOptConditionOrder) -> Result<OrderResult> {
+        block_on(AsyncCoreSDK::place_futopt_condition_order(self, account, condition_order))
+    }
+    
+    fn modify_price(&self, account: &Account, order: OrderRef<'_>, price: Price) -> Result<OrderResult> {
+        block_on(AsyncCoreSDK::modify_price(self, account, order, price))
+    }
+    
+    fn modify_quantity(&self, account: &Account, order: OrderRef<'_>, quantity: u32) -> Result<OrderResult> {
+        block_on(AsyncCoreSDK::modify_quantity(self, account, order, quantity))
+    }
+    
+    fn cancel_order(&self, account: &Account, order_id: &str) -> Result<()> {
+        block_on(AsyncCoreSDK::cancel_order(self, account, order_id))
+    }
+    
+    fn cancel_orders(&self, account: &Account, order_ids: &[&str]) -> Result<Vec<Result<()>>> {
+        block_on(AsyncCoreSDK::cancel_orders(self, account, order_ids))
+    }
+    
+    fn get_order_status(&self, account: &Account, order_id: &str) -> Result<OrderResult> {
+        block_on(AsyncCoreSDK::get_order_status(self, account, order_id))
+    }
+    
+    fn get_order_results(&self, account: &Account) -> Result<Vec<OrderResult>> {
+        block_on(AsyncCoreSDK::get_order_results(self, account))
+    }
+    
+    fn get_order_history(
+        &self,
+        account: &Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        status: Option<HistoryStatus>,
+    ) -> Result<Vec<OrderResult>> {
+        block_on(AsyncCoreSDK::get_order_history(self, account, from, to, status))
+    }
+    
+    fn get_filled_history(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<FillRecord>> {
+        block_on(AsyncCoreSDK::get_filled_history(self, account, from, to))
+    }
+    
+    fn get_realized_pnl(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<RealizedPnl>> {
+        block_on(AsyncCoreSDK::get_realized_pnl(self, account, from, to))
+    }
+    
+    fn get_unrealized_pnl(&self, account: &Account) -> Result<Vec<UnrealizedPnl>> {
+        block_on(AsyncCoreSDK::get_unrealized_pnl(self, account))
+    }
+    
+    fn get_pnl_report(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<PnlReport> {
+        block_on(AsyncCoreSDK::get_pnl_report(self, account, from, to))
+    }
+    
+    fn get_account_balance(&self, account: &Account) -> Result<Balance> {
+        block_on(AsyncCoreSDK::get_account_balance(self, account))
+    }
+    
+    fn get_futopt_equity(&self, account: &Account) -> Result<FutOptEquity> {
+        block_on(AsyncCoreSDK::get_futopt_equity(self, account))
+    }
+    
+    fn get_futopt_positions(&self, account: &Account) -> Result<FutOptPositions> {
+        block_on(AsyncCoreSDK::get_futopt_positions(self, account))
+    }
+    
+    fn get_margin_quota(&self, account: &Account, symbol: &str) -> Result<MarginQuota> {
+        block_on(AsyncCoreSDK::get_margin_quota(self, account, symbol))
+    }
+    
+    fn get_positions(&self, account: &Account) -> Result<Vec<Position>> {
+        block_on(AsyncCoreSDK::get_positions(self, account))
+    }
 }
 
 /// Main Fubon SDK implementation
 pub struct FubonSDK {
-    credentials: Option<LoginCredentials>,
-    accounts: Vec<Account>,
+    backend: Arc<dyn Backend>,
+    throttle: Arc<OrderThrottle>,
+    risk: Arc<RiskGuard>,
+    session: Arc<Session>,
     market_data: Option<MarketData>,
-    is_logged_in: bool,
+    /// Stops the background realtime token refresh when dropped
+    token_refresher: Option<DropGuard>,
+    quota_check: bool,
 }
 
 impl FubonSDK {
-    /// Create a new SDK instance
+    /// Create a new SDK instance using the default backend
     pub fn new() -> Self {
+        Self::with_backend(default_backend())
+    }
+    
+    /// Create a new SDK instance delegating to a specific backend
+    pub fn with_backend(backend: Arc<dyn Backend>) -> Self {
         Self {
-            credentials: None,
-            accounts: Vec::new(),
+            backend,
+            throttle: Arc::new(OrderThrottle::new(ThrottleConfig::new())),
+            risk: Arc::new(RiskGuard::default()),
+            session: Arc::new(Session::new()),
             market_data: None,
-            is_logged_in: false,
+            token_refresher: None,
+            quota_check: false,
         }
     }
     
+    /// Create a new SDK instance talking to a custom trading API endpoint
+    #[cfg(feature = "http")]
+    pub fn with_trade_config(trade_config: TradeConfig) -> Self {
+        Self::with_backend(Arc::new(HttpBackend::new(trade_config)))
+    }
+    
+    /// Set the concurrency and rate limits for order submissions
+    pub fn with_throttle(mut self, config: ThrottleConfig) -> Self {
+        self.throttle = Arc::new(OrderThrottle::new(config));
+        self
+    }
+    
+    /// Set the pre-trade risk limits checked before orders are sent
+    pub fn with_risk_limits(mut self, limits: RiskLimits) -> Self {
+        self.risk = Arc::new(RiskGuard::new(limits));
+        self
+    }
+    
+    /// Check margin buys and short sells against the symbol's quota before
+    /// sending them, at the cost of one extra query per such order
+    pub fn with_quota_check(mut self, enabled: bool) -> Self {
+        self.quota_check = enabled;
+        self
+    }
+    
+    /// Get the order submission limits
+    pub fn throttle_config(&self) -> &ThrottleConfig {
+        self.throttle.config()
+    }
+    
+    /// Get the pre-trade risk guard, e.g. to feed it last trade prices by
+    /// registering it with a [`WebSocketClient`](crate::WebSocketClient), or to
+    /// set the reference prices stock orders are checked against the daily limits with
+    pub fn risk_guard(&self) -> &Arc<RiskGuard> {
+        &self.risk
+    }
+    
+    /// Get the trading backend
+    pub fn backend(&self) -> &Arc<dyn Backend> {
+        &self.backend
+    }
+    
+    /// Get the trading event hub of the backend
+    pub fn trade_events(&self) -> TradeEvents {
+        self.backend.events()
+    }
+    
+    /// Register callbacks for order acknowledgements, changes and fills
+    pub fn add_trade_event_handler(&self, handler: Arc<dyn TradeEventHandler>) {
+        self.backend.events().add_handler(handler);
+    }
+    
+    /// Receive order acknowledgements, changes and fills as an async stream
+    pub fn trade_event_stream(&self) -> BoxStream<'static, TradeEvent> {
+        self.backend.events().stream()
+    }
+    
     /// Get available accounts (must login first)
-    pub fn accounts(&self) -> &[Account] {
-        &self.accounts
+    pub fn accounts(&self) -> Vec<Account> {
+        self.session.accounts()
     }
     
-    /// Check if logged in
+    /// Check if logged in; an expired session is renewed on the next call
     pub fn is_logged_in(&self) -> bool {
-        self.is_logged_in
+        self.session.is_logged_in()
     }
     
-    /// Initialize realtime market data
+    /// Get the login session, to inspect its expiry or listen for session events
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+    
+    /// Initialize realtime market data.
+    ///
+    /// A token the broker issues with an expiry is refreshed in the background
+    /// ahead of it and pushed into the market data clients.
     pub fn init_realtime(&mut self, mode: Mode) -> Result<()> {
-        let sdk_token = self.exchange_realtime_token()?;
-        self.market_data = Some(MarketData::new(sdk_token, mode)?);
+        let token = block_on(self.realtime_token())?;
+        let expires = token.expires_at.is_some();
+        let market_data = MarketData::with_token(token, mode)?;
+        
+        self.token_refresher = None;
+        if expires {
+            let refresher = spawn_token_refresher(
+                Arc::clone(&self.backend),
+                Arc::clone(&self.session),
+                market_data.shared_token(),
+            )?;
+            self.token_refresher = Some(refresher.drop_guard());
+        }
+        
+        self.market_data = Some(market_data);
         Ok(())
     }
     
+    /// Exchange a new realtime token now and push it into the market data clients
+    pub fn refresh_realtime_token(&self) -> Result<()> {
+        let market_data = self.market_data
+            .as_ref()
+            .ok_or_else(|| Error::general("Realtime market data is not initialized"))?;
+        
+        let token = block_on(self.realtime_token())?;
+        let expires_at = token.expires_at;
+        market_data.update_token(token);
+        self.session.emit(SessionEvent::RealtimeTokenRefreshed { expires_at });
+        Ok(())
+    }
+    
+    /// Time the realtime market data token expires, if known
+    pub fn realtime_token_expires_at(&self) -> Option<DateTime<Utc>> {
+        self.market_data.as_ref()?.token_expires_at()
+    }
+    
     /// Get market data instance
     pub fn market_data(&self) -> Option<&MarketData> {
         self.market_data.as_ref()
@@ -80,6 +459,120 @@ impl FubonSDK {
     pub fn market_data_mut(&mut self) -> Option<&mut MarketData> {
         self.market_data.as_mut()
     }
+    
+    /// Run a backend call with a live session, see [`Session::run`]
+    async fn authorized<T, F, Fut>(&self, call: F) -> Result<T>
+    where
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T>> + Send,
+        T: Send,
+    {
+        self.session.run(self.backend.as_ref(), call).await
+    }
+    
+    /// Exchange the login session for a realtime token
+    async fn realtime_token(&self) -> Result<RealtimeToken> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before accessing realtime data"));
+        }
+        
+        self.authorized(|| self.backend.exchange_realtime_token()).await
+    }
+    
+    /// Look up an account among those returned by login
+    fn known_account(&self, account: &Account) -> Result<Account> {
+        self.session
+            .accounts()
+            .into_iter()
+            .find(|a| a.account_id == account.account_id)
+            .ok_or_else(|| Error::general(format!(
+                "Account {} is not available for this login",
+                account.account_id
+            )))
+    }
+    
+    /// Check that an account can place stock orders
+    fn stock_account(&self, account: &Account) -> Result<()> {
+        let account = self.known_account(account)?;
+        if !account.is_stock() {
+            return Err(Error::account_mismatch(&account, "stock orders"));
+        }
+        Ok(())
+    }
+    
+    /// Check that an account can place futures/options orders
+    fn futopt_account(&self, account: &Account) -> Result<()> {
+        let account = self.known_account(account)?;
+        if !account.is_futopt() {
+            return Err(Error::account_mismatch(&account, "futures/options orders"));
+        }
+        Ok(())
+    }
+    
+    /// Run the pre-trade risk rules on an order, returning its reserved notional
+    async fn check_risk(&self, account: &Account, request: &OrderRequest) -> Result<Amount> {
+        let position = if self.risk.needs_position(request.symbol()) {
+            Some(self.net_position(account, request.symbol(), request.is_futopt()).await?)
+        } else {
+            None
+        };
+        
+        self.risk.check(request, position)
+    }
+    
+    /// Signed net position in a symbol, positive when long
+    async fn net_position(&self, account: &Account, symbol: &str, futopt: bool) -> Result<i64> {
+        let positions = if futopt {
+            self.authorized(|| self.backend.get_futopt_positions(account)).await?.single
+        } else {
+            self.authorized(|| self.backend.get_positions(account)).await?
+        };
+        
+        Ok(positions
+            .iter()
+            .filter(|position| position.symbol == symbol)
+            .map(|position| match position.action {
+                BSAction::Buy => position.quantity as i64,
+                BSAction::Sell => -(position.quantity as i64),
+            })
+            .sum())
+    }
+    
+    /// Fetch the latest state of an order that is about to be modified
+    async fn working_order(&self, account: &Account, order: OrderRef<'_>) -> Result<OrderResult> {
+        if order.order_no().is_empty() {
+            return Err(Error::general("Order ID cannot be empty"));
+        }
+        
+        let trading_account = self.known_account(account)?;
+        let current = self.authorized(|| self.backend.get_order_status(account, order.order_no())).await?;
+        
+        if current.request.is_futopt() != trading_account.is_futopt() {
+            let instrument = if current.request.is_futopt() { "futures/options orders" } else { "stock orders" };
+            return Err(Error::account_mismatch(&trading_account, instrument));
+        }
+        
+        if !current.status.is_active() {
+            return Err(Error::general(format!(
+                "Order {} is no longer working ({:?})",
+                current.order_no, current.status
+            )));
+        }
+        
+        if current.request.is_condition() {
+            return Err(Error::general("Conditional orders cannot be modified"));
+        }
+        
+        Ok(current)
+    }
+}
+
+/// Check that a history query covers at least one day
+fn validate_date_range(from: NaiveDate, to: NaiveDate) -> Result<()> {
+    if from > to {
+        return Err(Error::general(format!("Start date {} is after end date {}", from, to)));
+    }
+    Ok(())
 }
 
 impl Default for FubonSDK {
@@ -88,11 +581,9 @@ impl Default for FubonSDK {
     }
 }
 
-impl CoreSDK for FubonSDK {
-    fn login(&mut self, credentials: LoginCredentials) -> Result<Vec<Account>> {
-        // This would normally make an API call to authenticate with certificate
-        // For now, return placeholder accounts
-        
+#[async_trait]
+impl AsyncCoreSDK for FubonSDK {
+    async fn login(&mut self, credentials: LoginCredentials) -> Result<Vec<Account>> {
         // Validate credentials
         if credentials.personal_id.is_empty() {
             return Err(Error::general("Personal ID cannot be empty"));
@@ -106,63 +597,38 @@ impl CoreSDK for FubonSDK {
             return Err(Error::general("Certificate path cannot be empty"));
         }
         
-        // In a real implementation, this would:
-        // 1. Load the certificate from cert_path
-        // 2. Make HTTPS request to Fubon API with certificate authentication
-        // 3. Authenticate with personal_id and password
-        // 4. Return the list of available accounts
-        
-        // Placeholder accounts
-        let accounts = vec![
-            Account {
-                account_id: "1234567890".to_string(),
-                account_name: "Main Trading Account".to_string(),
-                account_type: "stock".to_string(),
-                status: "active".to_string(),
-                currency: "TWD".to_string(),
-                available_balance: Some(100000.0),
-                total_balance: Some(120000.0),
-            },
-            Account {
-                account_id: "0987654321".to_string(),
-                account_name: "Futures Account".to_string(),
-                account_type: "future".to_string(),
-                status: "active".to_string(),
-                currency: "TWD".to_string(),
-                available_balance: Some(50000.0),
-                total_balance: Some(60000.0),
-            },
-        ];
-        
-        self.credentials = Some(credentials);
-        self.accounts = accounts.clone();
-        self.is_logged_in = true;
-        
-        Ok(accounts)
+        let login = self.backend.login(&credentials).await?;
+        
+        self.session.start(credentials, &login);
+        
+        Ok(login.accounts)
     }
     
-    fn exchange_realtime_token(&self) -> Result<String> {
-        // This would normally make an API call to get realtime token after login
-        // For now, return a placeholder
-        if !self.is_logged_in {
-            return Err(Error::general("Must login first before accessing realtime data"));
+    async fn logout(&mut self) -> Result<()> {
+        if !self.is_logged_in() {
+            return Ok(());
         }
         
-        // In a real implementation, this would:
-        // 1. Make HTTP request to Fubon API using login session
-        // 2. Exchange login session for realtime token
-        // 3. Return the token
+        // Forget the session even if the broker could not be reached
+        let result = self.backend.logout().await;
+        self.session.end();
+        self.token_refresher = None;
+        self.market_data = None;
         
-        Ok("placeholder_realtime_token".to_string())
+        result
+    }
+    
+    async fn exchange_realtime_token(&self) -> Result<String> {
+        Ok(self.realtime_token().await?.token)
     }
     
-    fn place_order(&self, order: &Order) -> Result<String> {
-        // This would normally make an API call to place the order
-        // For now, return a placeholder order ID
-        if !self.is_logged_in {
+    async fn place_order(&self, account: &Account, order: &Order) -> Result<OrderResult> {
+        if !self.is_logged_in() {
             return Err(Error::general("Must login first before placing orders"));
         }
         
+        self.stock_account(account)?;
+        
         // Validate order
         if order.symbol.is_empty() {
             return Err(Error::general("Symbol cannot be empty"));
@@ -172,19 +638,52 @@ impl CoreSDK for FubonSDK {
             return Err(Error::general("Quantity must be greater than 0"));
         }
         
-        // In a real implementation, this would:
-        // 1. Make HTTP request to Fubon API
-        // 2. Submit the order
-        // 3. Return the order ID
+        order.validate()?;
+        order.validate_price(self.risk.reference_price(&order.symbol))?;
         
-        Ok(format!("order_{}", chrono::Utc::now().timestamp()))
+        if self.quota_check && order.uses_margin_quota() {
+            let quota = AsyncCoreSDK::get_margin_quota(self, account, &order.symbol).await?;
+            quota.check(order)?;
+        }
+        
+        let notional = self.check_risk(account, &OrderRequest::Stock(order.clone())).await?;
+        
+        self.throttle.acquire().await;
+        let result = self.authorized(|| self.backend.place_order(account, order)).await;
+        if result.is_err() {
+            self.risk.release(notional);
+        }
+        result
+    }
+    
+    async fn place_orders(&self, account: &Account, orders: &[Order]) -> Result<Vec<Result<OrderResult>>> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before placing orders"));
+        }
+        
+        self.stock_account(account)?;
+        
+        // Futures are lazy, so `buffered` bounds how many are in flight
+        let submissions: Vec<_> = orders
+            .iter()
+            .map(|order| AsyncCoreSDK::place_order(self, account, order))
+            .collect();
+        
+        let results = stream::iter(submissions)
+            .buffered(self.throttle.config().max_concurrency())
+            .collect()
+            .await;
+        
+        Ok(results)
     }
     
-    fn place_condition_order(&self, condition_order: &ConditionOrder) -> Result<String> {
-        if !self.is_logged_in {
+    async fn place_condition_order(&self, account: &Account, condition_order: &ConditionOrder) -> Result<OrderResult> {
+        if !self.is_logged_in() {
             return Err(Error::general("Must login first before placing condition orders"));
         }
         
+        self.stock_account(account)?;
+        
         // Validate condition order
         if condition_order.order.symbol.is_empty() {
             return Err(Error::general("Symbol cannot be empty"));
@@ -194,14 +693,26 @@ impl CoreSDK for FubonSDK {
             return Err(Error::general("Condition symbol cannot be empty"));
         }
         
-        Ok(format!("condition_order_{}", chrono::Utc::now().timestamp()))
+        condition_order.validate()?;
+        condition_order.order.validate_price(self.risk.reference_price(&condition_order.order.symbol))?;
+        
+        let notional = self.check_risk(account, &OrderRequest::Condition(condition_order.clone())).await?;
+        
+        self.throttle.acquire().await;
+        let result = self.authorized(|| self.backend.place_condition_order(account, condition_order)).await;
+        if result.is_err() {
+            self.risk.release(notional);
+        }
+        result
     }
     
-    fn place_futopt_order(&self, order: &FutOptOrder) -> Result<String> {
-        if !self.is_logged_in {
+    async fn place_futopt_order(&self, account: &Account, order: &FutOptOrder) -> Result<OrderResult> {
+        if !self.is_logged_in() {
             return Err(Error::general("Must login first before placing futures/options orders"));
         }
         
+        self.futopt_account(account)?;
+        
         // Validate future/option order
         if order.symbol.is_empty() {
             return Err(Error::general("Symbol cannot be empty"));
@@ -211,14 +722,25 @@ impl CoreSDK for FubonSDK {
             return Err(Error::general("Quantity must be greater than 0"));
         }
         
-        Ok(format!("futopt_order_{}", chrono::Utc::now().timestamp()))
+        order.validate()?;
+        
+        let notional = self.check_risk(account, &OrderRequest::FutOpt(order.clone())).await?;
+        
+        self.throttle.acquire().await;
+        let result = self.authorized(|| self.backend.place_futopt_order(account, order)).await;
+        if result.is_err() {
+            self.risk.release(notional);
+        }
+        result
     }
     
-    fn place_futopt_condition_order(&self, condition_order: &FutOptConditionOrder) -> Result<String> {
-        if !self.is_logged_in {
+    async fn place_futopt_condition_order(&self, account: &Account, condition_order: &FutOptConditionOrder) -> Result<OrderResult> {
+        if !self.is_logged_in() {
             return Err(Error::general("Must login first before placing futures/options condition orders"));
         }
         
+        self.futopt_account(account)?;
+        
         // Validate future/option condition order
         if condition_order.order.symbol.is_empty() {
             return Err(Error::general("Symbol cannot be empty"));
@@ -228,50 +750,259 @@ impl CoreSDK for FubonSDK {
             return Err(Error::general("Condition symbol cannot be empty"));
         }
         
-        Ok(format!("futopt_condition_order_{}", chrono::Utc::now().timestamp()))
+        condition_order.validate()?;
+        
+        let notional = self.check_risk(account, &OrderRequest::FutOptCondition(condition_order.clone())).await?;
+        
+        self.throttle.acquire().await;
+        let result = self.authorized(|| self.backend.place_futopt_condition_order(account, condition_order)).await;
+        if result.is_err() {
+            self.risk.release(notional);
+        }
+        result
+    }
+    
+    async fn modify_price(&self, account: &Account, order: OrderRef<'_>, price: Price) -> Result<OrderResult> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before modifying orders"));
+        }
+        
+        if !price.is_positive() {
+            return Err(Error::general("Price must be greater than 0"));
+        }
+        
+        let current = self.working_order(account, order).await?;
+        if current.request.is_market() {
+            return Err(Error::general("Cannot modify the price of a market order"));
+        }
+        
+        if !current.request.is_futopt() {
+            let symbol = current.request.symbol();
+            tick::validate_price(symbol, price, self.risk.reference_price(symbol))?;
+        }
+        
+        // A rise in notional is reserved now, a fall given back once accepted
+        let change = self.risk.check_reprice(&current, price)?;
+        
+        self.throttle.acquire().await;
+        let result = self.authorized(|| self.backend.modify_price(account, &current, price)).await;
+        match &result {
+            Err(_) if change.is_positive() => self.risk.release(change),
+            Ok(_) if change.is_negative() => self.risk.release(-change),
+            _ => {}
+        }
+        result
     }
     
-    fn cancel_order(&self, order_id: &str) -> Result<()> {
-        if !self.is_logged_in {
+    async fn modify_quantity(&self, account: &Account, order: OrderRef<'_>, quantity: u32) -> Result<OrderResult> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before modifying orders"));
+        }
+        
+        let current = self.working_order(account, order).await?;
+        let total = current.filled_quantity + current.remaining_quantity;
+        
+        if quantity < current.filled_quantity {
+            return Err(Error::general(format!(
+                "New quantity {} is below the filled quantity {}",
+                quantity, current.filled_quantity
+            )));
+        }
+        
+        if quantity >= total {
+            return Err(Error::general(format!(
+                "New quantity {} must be below the current quantity {}",
+                quantity, total
+            )));
+        }
+        
+        // Outside the odd-lot sessions what is left must still be whole lots
+        if let OrderRequest::Stock(stock) | OrderRequest::Condition(ConditionOrder { order: stock, .. }) = &current.request {
+            if quantity > 0 {
+                stock.session.validate_quantity(quantity)?;
+            }
+        }
+        
+        self.throttle.acquire().await;
+        self.authorized(|| self.backend.modify_quantity(account, &current, quantity)).await
+    }
+    
+    async fn cancel_order(&self, account: &Account, order_id: &str) -> Result<()> {
+        if !self.is_logged_in() {
             return Err(Error::general("Must login first before canceling orders"));
         }
         
+        self.known_account(account)?;
+        
         if order_id.is_empty() {
             return Err(Error::general("Order ID cannot be empty"));
         }
         
-        // In a real implementation, this would make an API call to cancel the order
-        Ok(())
+        self.throttle.acquire().await;
+        self.authorized(|| self.backend.cancel_order(account, order_id)).await
+    }
+    
+    async fn cancel_orders(&self, account: &Account, order_ids: &[&str]) -> Result<Vec<Result<()>>> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before canceling orders"));
+        }
+        
+        self.known_account(account)?;
+        
+        let cancellations: Vec<_> = order_ids
+            .iter()
+            .map(|order_id| AsyncCoreSDK::cancel_order(self, account, order_id))
+            .collect();
+        
+        let results = stream::iter(cancellations)
+            .buffered(self.throttle.config().max_concurrency())
+            .collect()
+            .await;
+        
+        Ok(results)
     }
     
-    fn get_order_status(&self, order_id: &str) -> Result<String> {
-        if !self.is_logged_in {
+    async fn get_order_status(&self, account: &Account, order_id: &str) -> Result<OrderResult> {
+        if !self.is_logged_in() {
             return Err(Error::general("Must login first before checking order status"));
         }
         
+        self.known_account(account)?;
+        
         if order_id.is_empty() {
             return Err(Error::general("Order ID cannot be empty"));
         }
         
-        // In a real implementation, this would make an API call to get order status
-        Ok("FILLED".to_string())
+        self.authorized(|| self.backend.get_order_status(account, order_id)).await
+    }
+    
+    async fn get_order_results(&self, account: &Account) -> Result<Vec<OrderResult>> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before checking order results"));
+        }
+        
+        self.known_account(account)?;
+        
+        self.authorized(|| self.backend.get_order_results(account)).await
+    }
+    
+    async fn get_order_history(
+        &self,
+        account: &Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        status: Option<HistoryStatus>,
+    ) -> Result<Vec<OrderResult>> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before checking order history"));
+        }
+        
+        self.known_account(account)?;
+        validate_date_range(from, to)?;
+        
+        self.authorized(|| self.backend.get_order_history(account, from, to, status)).await
     }
     
-    fn get_account_balance(&self) -> Result<f64> {
-        if !self.is_logged_in {
+    async fn get_filled_history(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<FillRecord>> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before checking filled history"));
+        }
+        
+        self.known_account(account)?;
+        validate_date_range(from, to)?;
+        
+        self.authorized(|| self.backend.get_filled_history(account, from, to)).await
+    }
+    
+    async fn get_realized_pnl(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<Vec<RealizedPnl>> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before checking profit and loss"));
+        }
+        
+        self.known_account(account)?;
+        validate_date_range(from, to)?;
+        
+        self.authorized(|| self.backend.get_realized_pnl(account, from, to)).await
+    }
+    
+    async fn get_unrealized_pnl(&self, account: &Account) -> Result<Vec<UnrealizedPnl>> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before checking profit and loss"));
+        }
+        
+        self.known_account(account)?;
+        
+        self.authorized(|| self.backend.get_unrealized_pnl(account)).await
+    }
+    
+    async fn get_pnl_report(&self, account: &Account, from: NaiveDate, to: NaiveDate) -> Result<PnlReport> {
+        let (realized, unrealized) = futures_util::try_join!(
+            AsyncCoreSDK::get_realized_pnl(self, account, from, to),
+            AsyncCoreSDK::get_unrealized_pnl(self, account),
+        )?;
+        
+        let account = self.known_account(account)?;
+        Ok(PnlReport::new(
+            account.account_id.clone(),
+            account.currency.clone(),
+            from,
+            to,
+            realized,
+            unrealized,
+        ))
+    }
+    
+    async fn get_account_balance(&self, account: &Account) -> Result<Balance> {
+        if !self.is_logged_in() {
             return Err(Error::general("Must login first before checking account balance"));
         }
         
-        // In a real implementation, this would make an API call to get account balance
-        Ok(100000.0)
+        self.known_account(account)?;
+        
+        self.authorized(|| self.backend.get_account_balance(account)).await
     }
     
-    fn get_positions(&self) -> Result<Vec<String>> {
-        if !self.is_logged_in {
+    async fn get_futopt_equity(&self, account: &Account) -> Result<FutOptEquity> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before checking equity"));
+        }
+        
+        self.futopt_account(account)?;
+        
+        self.authorized(|| self.backend.get_futopt_equity(account)).await
+    }
+    
+    async fn get_futopt_positions(&self, account: &Account) -> Result<FutOptPositions> {
+        if !self.is_logged_in() {
             return Err(Error::general("Must login first before checking positions"));
         }
         
-        // In a real implementation, this would make an API call to get positions
-        Ok(vec!["AAPL".to_string(), "TSLA".to_string()])
+        self.futopt_account(account)?;
+        
+        self.authorized(|| self.backend.get_futopt_positions(account)).await
+    }
+    
+    async fn get_margin_quota(&self, account: &Account, symbol: &str) -> Result<MarginQuota> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before checking margin quota"));
+        }
+        
+        self.stock_account(account)?;
+        
+        if symbol.is_empty() {
+            return Err(Error::general("Symbol cannot be empty"));
+        }
+        
+        self.authorized(|| self.backend.get_margin_quota(account, symbol)).await
+    }
+    
+    async fn get_positions(&self, account: &Account) -> Result<Vec<Position>> {
+        if !self.is_logged_in() {
+            return Err(Error::general("Must login first before checking positions"));
+        }
+        
+        self.known_account(account)?;
+        
+        self.authorized(|| self.backend.get_positions(account)).await
     }
 }
\ No newline at end of file
@@ -0,0 +1,292 @@
+//! Login session lifecycle: expiry tracking, automatic re-login and session events
+
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::{Result, Error};
+use crate::backend::Backend;
+use crate::constants::{SESSION_EVENT_CAPACITY, SESSION_RENEW_MARGIN};
+use crate::types::{Account, LoginCredentials};
+
+/// Session returned by a backend login
+#[derive(Debug, Clone)]
+pub struct LoginSession {
+    /// Accounts available to the logged in user
+    pub accounts: Vec<Account>,
+    /// Time the session token expires, if the broker reports it
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Realtime market data token exchanged from the login session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RealtimeToken {
+    pub token: String,
+    /// Time the token expires, if the broker reports it
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl RealtimeToken {
+    pub fn new(token: String, expires_at: Option<DateTime<Utc>>) -> Self {
+        Self { token, expires_at }
+    }
+}
+
+/// Current state of the login session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    /// Never logged in, or logged out
+    LoggedOut,
+    /// Logged in with a valid session
+    Active,
+    /// Session expired or rejected by the broker; renewed on the next call
+    Expired,
+}
+
+/// Session state change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionEvent {
+    LoggedIn { expires_at: Option<DateTime<Utc>> },
+    Expired,
+    Renewed { expires_at: Option<DateTime<Utc>> },
+    RenewFailed(String),
+    LoggedOut,
+    /// Realtime market data token replaced ahead of its expiry
+    RealtimeTokenRefreshed { expires_at: Option<DateTime<Utc>> },
+    /// Realtime market data token could not be replaced; retried until it lapses
+    RealtimeTokenRefreshFailed(String),
+}
+
+/// Session event handler trait
+pub trait SessionEventHandler: Send + Sync {
+    fn handle_event(&self, event: &SessionEvent);
+}
+
+struct SessionInner {
+    state: SessionState,
+    credentials: Option<LoginCredentials>,
+    /// Accounts returned by the last (re-)login
+    accounts: Vec<Account>,
+    logged_in_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+    /// Incremented on every (re-)login so concurrent callers renew only once
+    generation: u64,
+}
+
+/// Login session of a [`FubonSDK`](crate::FubonSDK).
+///
+/// The credentials of the last login are kept so that an expired session, or
+/// one the broker reports as invalid, can be renewed without user action. The
+/// account list is replaced by the one each re-login returns.
+pub struct Session {
+    inner: Mutex<SessionInner>,
+    renewal: tokio::sync::Mutex<()>,
+    handlers: RwLock<Vec<Arc<dyn SessionEventHandler>>>,
+    sender: broadcast::Sender<SessionEvent>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(SESSION_EVENT_CAPACITY);
+        Self {
+            inner: Mutex::new(SessionInner {
+                state: SessionState::LoggedOut,
+                credentials: None,
+                accounts: Vec::new(),
+                logged_in_at: None,
+                expires_at: None,
+                generation: 0,
+            }),
+            renewal: tokio::sync::Mutex::new(()),
+            handlers: RwLock::new(Vec::new()),
+            sender,
+        }
+    }
+
+    /// Current session state
+    pub fn state(&self) -> SessionState {
+        self.inner().state
+    }
+
+    /// Whether a login is in effect (the session may need renewal)
+    pub fn is_logged_in(&self) -> bool {
+        self.state() != SessionState::LoggedOut
+    }
+
+    /// Accounts returned by the last successful (re-)login
+    pub fn accounts(&self) -> Vec<Account> {
+        self.inner().accounts.clone()
+    }
+
+    /// Time of the last successful (re-)login
+    pub fn logged_in_at(&self) -> Option<DateTime<Utc>> {
+        self.inner().logged_in_at
+    }
+
+    /// Time the current session token expires, if known
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.inner().expires_at
+    }
+
+    /// Add event handler
+    pub fn add_handler(&self, handler: Arc<dyn SessionEventHandler>) {
+        if let Ok(mut handlers) = self.handlers.write() {
+            handlers.push(handler);
+        }
+    }
+
+    /// Receive all future session events
+    pub fn subscribe(&self) -> broadcast::Receiver<SessionEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Record a successful login
+    pub(crate) fn start(&self, credentials: LoginCredentials, login: &LoginSession) {
+        {
+            let mut inner = self.inner();
+            inner.state = SessionState::Active;
+            inner.credentials = Some(credentials);
+            inner.accounts = login.accounts.clone();
+            inner.logged_in_at = Some(Utc::now());
+            inner.expires_at = login.expires_at;
+            inner.generation += 1;
+        }
+        self.emit(SessionEvent::LoggedIn { expires_at: login.expires_at });
+    }
+
+    /// Forget the session and its credentials
+    pub(crate) fn end(&self) {
+        {
+            let mut inner = self.inner();
+            inner.state = SessionState::LoggedOut;
+            inner.credentials = None;
+            inner.accounts.clear();
+            inner.logged_in_at = None;
+            inner.expires_at = None;
+            inner.generation += 1;
+        }
+        self.emit(SessionEvent::LoggedOut);
+    }
+
+    /// Get a usable session, renewing it first if it has expired or is about to.
+    ///
+    /// Returns the session generation to pass to [`Session::invalidate`].
+    pub(crate) async fn ensure_active(&self, backend: &dyn Backend) -> Result<u64> {
+        let (state, expires_at, generation) = {
+            let inner = self.inner();
+            (inner.state, inner.expires_at, inner.generation)
+        };
+
+        let expiring = expires_at
+            .map(|expires_at| expires_at - Duration::seconds(SESSION_RENEW_MARGIN) <= Utc::now())
+            .unwrap_or(false);
+
+        match state {
+            SessionState::LoggedOut => Err(Error::Unauthenticated),
+            SessionState::Active if !expiring => Ok(generation),
+            _ => self.renew(backend, generation).await,
+        }
+    }
+
+    /// Handle a session the broker rejected, renewing it unless another caller already did
+    pub(crate) async fn invalidate(&self, backend: &dyn Backend, generation: u64) -> Result<u64> {
+        let expired = {
+            let mut inner = self.inner();
+            let expired = inner.generation == generation && inner.state == SessionState::Active;
+            if expired {
+                inner.state = SessionState::Expired;
+            }
+            expired
+        };
+
+        if expired {
+            self.emit(SessionEvent::Expired);
+        }
+
+        self.renew(backend, generation).await
+    }
+
+    /// Run a backend call with a live session.
+    ///
+    /// An expired session is renewed before the call. If the broker rejects
+    /// the session, the stored credentials are used to log in again and the
+    /// call is retried once.
+    pub(crate) async fn run<T, F, Fut>(&self, backend: &dyn Backend, call: F) -> Result<T>
+    where
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T>> + Send,
+        T: Send,
+    {
+        let generation = self.ensure_active(backend).await?;
+
+        match call().await {
+            Err(Error::Unauthenticated) => {
+                self.invalidate(backend, generation).await?;
+                call().await
+            }
+            result => result,
+        }
+    }
+
+    async fn renew(&self, backend: &dyn Backend, generation: u64) -> Result<u64> {
+        let _renewal = self.renewal.lock().await;
+
+        let credentials = {
+            let inner = self.inner();
+            if inner.generation != generation {
+                // Renewed (or logged out) while waiting for the lock
+                return match inner.state {
+                    SessionState::LoggedOut => Err(Error::Unauthenticated),
+                    _ => Ok(inner.generation),
+                };
+            }
+            inner.credentials.clone().ok_or(Error::Unauthenticated)?
+        };
+
+        match backend.login(&credentials).await {
+            Ok(login) => {
+                let generation = {
+                    let mut inner = self.inner();
+                    inner.state = SessionState::Active;
+                    inner.accounts = login.accounts;
+                    inner.logged_in_at = Some(Utc::now());
+                    inner.expires_at = login.expires_at;
+                    inner.generation += 1;
+                    inner.generation
+                };
+                self.emit(SessionEvent::Renewed { expires_at: login.expires_at });
+                Ok(generation)
+            }
+            Err(e) => {
+                self.inner().state = SessionState::Expired;
+                self.emit(SessionEvent::RenewFailed(e.to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    pub(crate) fn emit(&self, event: SessionEvent) {
+        if let Ok(handlers) = self.handlers.read() {
+            for handler in handlers.iter() {
+                handler.handle_event(&event);
+            }
+        }
+
+        // No subscribers is not an error
+        let _ = self.sender.send(event);
+    }
+
+    fn inner(&self) -> MutexGuard<'_, SessionInner> {
+        // The state stays consistent even if a holder panicked
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
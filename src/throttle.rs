@@ -0,0 +1,113 @@
+//! Order submission limits shared by single and batch trading calls
+
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::{Result, Error};
+use crate::constants::{DEFAULT_ORDER_CONCURRENCY, DEFAULT_ORDER_RATE_LIMIT};
+
+/// Limits applied to order submissions.
+///
+/// Fields are set through the `with_*` methods, which reject zero limits with
+/// [`Error::InvalidParameter`].
+#[derive(Debug, Clone)]
+pub struct ThrottleConfig {
+    max_concurrency: usize,
+    max_orders_per_second: Option<u32>,
+}
+
+impl ThrottleConfig {
+    pub fn new() -> Self {
+        Self {
+            max_concurrency: DEFAULT_ORDER_CONCURRENCY,
+            max_orders_per_second: Some(DEFAULT_ORDER_RATE_LIMIT),
+        }
+    }
+
+    /// Keep at most `max_concurrency` requests of a batch in flight; 0 is
+    /// rejected, as a batch could never make progress
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Result<Self> {
+        if max_concurrency == 0 {
+            return Err(Error::invalid_parameter("max_concurrency", "must be at least 1"));
+        }
+        self.max_concurrency = max_concurrency;
+        Ok(self)
+    }
+
+    /// Send at most `orders_per_second` order requests per second; 0 is
+    /// rejected, use [`without_rate_limit`](Self::without_rate_limit) to turn
+    /// rate limiting off
+    pub fn with_rate_limit(mut self, orders_per_second: u32) -> Result<Self> {
+        if orders_per_second == 0 {
+            return Err(Error::invalid_parameter("orders_per_second", "must be at least 1, use without_rate_limit to turn it off"));
+        }
+        self.max_orders_per_second = Some(orders_per_second);
+        Ok(self)
+    }
+
+    pub fn without_rate_limit(mut self) -> Self {
+        self.max_orders_per_second = None;
+        self
+    }
+
+    /// Maximum number of requests a batch keeps in flight, always at least 1
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Maximum number of order requests per second, `None` for unlimited
+    pub fn max_orders_per_second(&self) -> Option<u32> {
+        self.max_orders_per_second
+    }
+
+    /// Minimum spacing between two order requests
+    fn interval(&self) -> Option<Duration> {
+        self.max_orders_per_second
+            .map(|rate| Duration::from_secs(1) / rate)
+    }
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Spaces order requests so that the configured rate is never exceeded
+#[derive(Debug)]
+pub(crate) struct OrderThrottle {
+    config: ThrottleConfig,
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl OrderThrottle {
+    pub(crate) fn new(config: ThrottleConfig) -> Self {
+        Self {
+            config,
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn config(&self) -> &ThrottleConfig {
+        &self.config
+    }
+
+    /// Wait until the next order request may be sent
+    pub(crate) async fn acquire(&self) {
+        let Some(interval) = self.config.interval() else {
+            return;
+        };
+
+        // Reserve a slot under the lock, then wait for it without holding it
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let now = Instant::now();
+            let slot = next_slot.map_or(now, |next| next.max(now));
+            *next_slot = Some(slot + interval);
+            slot
+        };
+
+        tokio::time::sleep_until(slot).await;
+    }
+}
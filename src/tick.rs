@@ -0,0 +1,131 @@
+//! Tick sizes and daily price limits of TWSE and TPEx listed securities
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, Error};
+use crate::decimal::{Decimal, Price};
+
+/// Daily price movement allowed from the reference price (漲跌幅), 10%
+pub const PRICE_LIMIT_RATIO: Decimal = Decimal::new(10, 2);
+
+/// Price ladder (升降單位) a security trades on; TWSE and TPEx share the same ladders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TickLadder {
+    /// Stocks: 0.01 below 10, 0.05 below 50, 0.1 below 100, 0.5 below 500,
+    /// 1 below 1000 and 5 from 1000
+    Stock,
+    /// ETFs and other funds: 0.01 below 50 and 0.05 from 50
+    Etf,
+}
+
+impl TickLadder {
+    /// Ladder of a symbol; ETF codes start with `00`, e.g. `0050` or `00878`
+    pub fn for_symbol(symbol: &str) -> Self {
+        if symbol.starts_with("00") {
+            TickLadder::Etf
+        } else {
+            TickLadder::Stock
+        }
+    }
+
+    /// Tick size at `price`
+    pub fn tick_size(&self, price: Price) -> Price {
+        let bands: &[(i64, Price)] = match self {
+            TickLadder::Stock => &[
+                (10, Decimal::new(1, 2)),
+                (50, Decimal::new(5, 2)),
+                (100, Decimal::new(1, 1)),
+                (500, Decimal::new(5, 1)),
+                (1000, Decimal::ONE),
+            ],
+            TickLadder::Etf => &[(50, Decimal::new(1, 2))],
+        };
+        let top = match self {
+            TickLadder::Stock => Decimal::new(5, 0),
+            TickLadder::Etf => Decimal::new(5, 2),
+        };
+
+        bands.iter()
+            .find(|(below, _)| price < Decimal::from(*below))
+            .map_or(top, |(_, tick)| *tick)
+    }
+
+    /// Whether `price` is positive and a whole number of ticks
+    pub fn is_valid(&self, price: Price) -> bool {
+        price.is_positive() && price.is_multiple_of(self.tick_size(price))
+    }
+
+    /// Highest valid price at or below `price`
+    pub fn round_down(&self, price: Price) -> Price {
+        let tick = self.tick_size(price);
+        (price / tick).floor() * tick
+    }
+
+    /// Lowest valid price at or above `price`
+    pub fn round_up(&self, price: Price) -> Price {
+        // Band boundaries are whole ticks of the band below, so this never skips one
+        let tick = self.tick_size(price);
+        (price / tick).ceil() * tick
+    }
+
+    /// Valid price nearest to `price`, rounding half-way prices up
+    pub fn round(&self, price: Price) -> Price {
+        let (down, up) = (self.round_down(price), self.round_up(price));
+        if up - price <= price - down {
+            up
+        } else {
+            down
+        }
+    }
+}
+
+/// Limit-up and limit-down prices (漲停/跌停) of a trading day
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PriceLimits {
+    /// Reference price (參考價) the limits are derived from
+    pub reference: Price,
+    pub limit_up: Price,
+    pub limit_down: Price,
+}
+
+impl PriceLimits {
+    /// Limits ±10% from `reference`, rounded inwards to the ladder
+    pub fn new(reference: Price, ladder: TickLadder) -> Self {
+        Self {
+            reference,
+            limit_up: ladder.round_down(reference * (Decimal::ONE + PRICE_LIMIT_RATIO)),
+            limit_down: ladder.round_up(reference * (Decimal::ONE - PRICE_LIMIT_RATIO)),
+        }
+    }
+
+    /// Whether `price` is within the limits
+    pub fn contains(&self, price: Price) -> bool {
+        (self.limit_down..=self.limit_up).contains(&price)
+    }
+}
+
+/// Check a limit price of `symbol` against its tick ladder and, when the
+/// reference price is known, against the daily price limits
+pub fn validate_price(symbol: &str, price: Price, reference: Option<Price>) -> Result<()> {
+    let ladder = TickLadder::for_symbol(symbol);
+    if !ladder.is_valid(price) {
+        return Err(Error::invalid_price(symbol, price, format!(
+            "not a multiple of the {} tick, nearest valid price is {}",
+            ladder.tick_size(price).normalize(),
+            ladder.round(price).normalize()
+        )));
+    }
+
+    if let Some(reference) = reference {
+        let limits = PriceLimits::new(reference, ladder);
+        if !limits.contains(price) {
+            return Err(Error::invalid_price(symbol, price, format!(
+                "outside the daily limits {} to {}",
+                limits.limit_down.normalize(),
+                limits.limit_up.normalize()
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use p12_keystore::{KeyStore, Pkcs12ImportPolicy};
+use reqwest::Identity;
+
+use crate::{Error, Result};
+
+/// Load a PKCS#12 certificate file and convert it into a TLS client identity
+pub fn load_identity(cert_path: &str, cert_pass: Option<&str>) -> Result<Identity> {
+    let data = std::fs::read(cert_path)
+        .map_err(|e| Error::certificate(format!("Failed to read {}: {}", cert_path, e)))?;
+
+    let keystore = KeyStore::from_pkcs12(&data, cert_pass.unwrap_or(""), Pkcs12ImportPolicy::Strict)
+        .map_err(|e| Error::certificate(format!("Failed to open {}: {}", cert_path, e)))?;
+
+    let (_, chain) = keystore
+        .private_key_chain()
+        .ok_or_else(|| Error::certificate(format!("No private key found in {}", cert_path)))?;
+
+    if chain.certs().is_empty() {
+        return Err(Error::certificate(format!("No certificate found in {}", cert_path)));
+    }
+
+    // rustls only accepts PEM identities, so re-encode the decrypted key and chain
+    let mut pem = pem_block("PRIVATE KEY", chain.key().as_der());
+    for cert in chain.certs() {
+        pem.push_str(&pem_block("CERTIFICATE", cert.as_der()));
+    }
+
+    Identity::from_pem(pem.as_bytes())
+        .map_err(|e| Error::certificate(format!("Invalid client identity in {}: {}", cert_path, e)))
+}
+
+fn pem_block(label: &str, der: &[u8]) -> String {
+    let encoded = STANDARD.encode(der);
+    let mut block = format!("-----BEGIN {}-----\n", label);
+
+    for line in encoded.as_bytes().chunks(64) {
+        // base64 output is always ASCII
+        block.push_str(std::str::from_utf8(line).unwrap());
+        block.push('\n');
+    }
+
+    block.push_str(&format!("-----END {}-----\n", label));
+    block
+}
\ No newline at end of file
@@ -0,0 +1,88 @@
+//! WebSocket listener for trading events pushed by the trading API
+
+use std::time::Duration;
+use futures_util::StreamExt;
+use tokio::runtime::Builder;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_util::sync::CancellationToken;
+
+use crate::{Result, Error};
+use crate::constants::TRADE_EVENT_RECONNECT_DELAY;
+use crate::events::{TradeEvent, TradeEvents};
+
+/// Listen for trading events until the returned token is cancelled.
+///
+/// The listener runs on its own thread and runtime so that it keeps going
+/// when the SDK is used from blocking code. Dropped connections are reported
+/// as [`TradeEvent::Error`] and re-established after a short delay.
+pub(crate) fn spawn_event_listener(url: String, token: String, events: TradeEvents) -> Result<CancellationToken> {
+    let cancel = CancellationToken::new();
+    let stop = cancel.clone();
+
+    std::thread::Builder::new()
+        .name("fubon-trade-events".to_string())
+        .spawn(move || {
+            let runtime = match Builder::new_current_thread().enable_all().build() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    events.emit(TradeEvent::Error(format!("Trade event listener failed to start: {}", e)));
+                    return;
+                }
+            };
+
+            runtime.block_on(async move {
+                loop {
+                    tokio::select! {
+                        _ = stop.cancelled() => break,
+                        result = listen(&url, &token, &events) => {
+                            let message = match result {
+                                Ok(()) => "Trade event stream closed".to_string(),
+                                Err(e) => format!("Trade event stream failed: {}", e),
+                            };
+                            events.emit(TradeEvent::Error(message));
+                        }
+                    }
+
+                    tokio::select! {
+                        _ = stop.cancelled() => break,
+                        _ = tokio::time::sleep(Duration::from_secs(TRADE_EVENT_RECONNECT_DELAY)) => {}
+                    }
+                }
+            });
+        })?;
+
+    Ok(cancel)
+}
+
+/// Connect once and forward events until the connection ends
+async fn listen(url: &str, token: &str, events: &TradeEvents) -> Result<()> {
+    let mut request = url
+        .into_client_request()
+        .map_err(|e| Error::websocket(format!("Invalid trade event URL: {}", e)))?;
+    let authorization = HeaderValue::from_str(&format!("Bearer {}", token))
+        .map_err(|_| Error::websocket("Session token is not a valid header value"))?;
+    request.headers_mut().insert("Authorization", authorization);
+
+    let (mut stream, _) = connect_async(request)
+        .await
+        .map_err(|e| Error::websocket(format!("Failed to connect: {}", e)))?;
+
+    while let Some(message) = stream.next().await {
+        match message {
+            Ok(Message::Text(text)) => {
+                // Other messages, e.g. heartbeats, are not trading events
+                if let Ok(event) = serde_json::from_str::<TradeEvent>(&text) {
+                    events.emit(event);
+                }
+            }
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(e) => return Err(Error::websocket(e.to_string())),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod cert;
+pub(crate) mod events;
+pub mod rest;
+
+pub use rest::{LoginResponse, TradeClient, TradeConfig};
\ No newline at end of file
@@ -0,0 +1,200 @@
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{Result, Error};
+use crate::constants::{DEFAULT_TRADE_BASE_URL, TRADE_REQUEST_TIMEOUT};
+use crate::trade::cert::load_identity;
+use crate::types::{Account, LoginCredentials};
+
+/// Configuration for the trading API client
+#[derive(Debug, Clone)]
+pub struct TradeConfig {
+    pub base_url: String,
+    pub timeout: Duration,
+}
+
+impl TradeConfig {
+    pub fn new() -> Self {
+        Self {
+            base_url: DEFAULT_TRADE_BASE_URL.to_string(),
+            timeout: Duration::from_secs(TRADE_REQUEST_TIMEOUT),
+        }
+    }
+
+    pub fn with_base_url(mut self, url: String) -> Self {
+        self.base_url = url;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Build a full endpoint URL from a path such as `/api/v1/login`
+    pub fn endpoint(&self, path: &str) -> String {
+        format!("{}{}", self.base_url.trim_end_matches('/'), path)
+    }
+
+    /// WebSocket URL of the trading event stream
+    pub fn events_url(&self) -> String {
+        let url = self.endpoint("/api/v1/events");
+        if let Some(rest) = url.strip_prefix("https://") {
+            format!("wss://{}", rest)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            format!("ws://{}", rest)
+        } else {
+            url
+        }
+    }
+}
+
+impl Default for TradeConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Response envelope used by the trading API, mirroring the Python SDK `Result`
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    is_success: bool,
+    #[serde(default)]
+    message: Option<String>,
+    data: Option<T>,
+}
+
+impl<T> ApiResponse<T> {
+    fn into_data(self) -> std::result::Result<T, String> {
+        if !self.is_success {
+            return Err(self.message.unwrap_or_else(|| "Request was not successful".to_string()));
+        }
+
+        self.data.ok_or_else(|| "Response contained no data".to_string())
+    }
+}
+
+/// Data returned by a successful login
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoginResponse {
+    /// Session token for subsequent trading requests
+    pub token: String,
+    /// Accounts available to the logged in user
+    pub accounts: Vec<Account>,
+    /// Session token expiry, if reported
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Trading API client authenticated with the user's client certificate
+#[derive(Clone)]
+pub struct TradeClient {
+    client: Client,
+    config: TradeConfig,
+    token: Option<String>,
+}
+
+impl TradeClient {
+    /// Load the certificate from `credentials` and build an HTTPS client for `config`
+    pub fn new(config: TradeConfig, credentials: &LoginCredentials) -> Result<Self> {
+        let identity = load_identity(&credentials.cert_path, credentials.cert_pass.as_deref())?;
+
+        let client = Client::builder()
+            .identity(identity)
+            .timeout(config.timeout)
+            .build()?;
+
+        Ok(Self {
+            client,
+            config,
+            token: None,
+        })
+    }
+
+    /// Get client configuration
+    pub fn config(&self) -> &TradeConfig {
+        &self.config
+    }
+
+    /// Get the session token (available after login)
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Run the login handshake and return the account list
+    pub async fn login(&mut self, credentials: &LoginCredentials) -> Result<LoginResponse> {
+        let url = self.config.endpoint("/api/v1/login");
+        let body = json!({
+            "personal_id": credentials.personal_id,
+            "password": credentials.password,
+            "sdk_version": crate::VERSION,
+        });
+
+        let response = self.client
+            .post(&url)
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let text = response.text().await?;
+
+        let envelope: ApiResponse<LoginResponse> = serde_json::from_str(&text)
+            .map_err(|_| Error::authentication(format!("Unexpected login response (HTTP {})", status)))?;
+
+        let login = envelope.into_data().map_err(Error::authentication)?;
+        self.token = Some(login.token.clone());
+
+        Ok(login)
+    }
+    
+    /// Send an authenticated GET request and return the response data
+    pub async fn get<T, Q>(&self, path: &str, query: &Q) -> Result<T>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    {
+        let request = self.client
+            .get(self.config.endpoint(path))
+            .query(query);
+
+        self.send(request).await
+    }
+    
+    /// Send an authenticated POST request and return the response data
+    pub async fn post<T, B>(&self, path: &str, body: &B) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let request = self.client
+            .post(self.config.endpoint(path))
+            .json(body);
+
+        self.send(request).await
+    }
+    
+    async fn send<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T> {
+        let token = self.token.as_deref().ok_or(Error::Unauthenticated)?;
+
+        let response = request
+            .bearer_auth(token)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(Error::Unauthenticated);
+        }
+
+        let text = response.text().await?;
+        let envelope: ApiResponse<T> = serde_json::from_str(&text)
+            .map_err(|_| Error::api(format!("Unexpected response (HTTP {})", status)))?;
+
+        envelope.into_data().map_err(Error::api)
+    }
+}
\ No newline at end of file
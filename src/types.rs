@@ -1,7 +1,61 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::{Result, Error};
+use crate::constants::{MARKET_UTC_OFFSET, STOCK_LOT_SIZE};
+use crate::decimal::{Amount, Price};
+
+/// Give an enum the names the Fubon API and the Python `fubon_neo` package use
+/// for it, shared by serde, `Display` and `FromStr`. Parsing accepts only the
+/// wire names and any listed aliases; an `else` variant catches unknown names.
+macro_rules! wire_enum {
+    ($name:ident { $($variant:ident => $wire:literal $(| $alias:literal)*,)* } $(else $other:ident)?) => {
+        impl $name {
+            /// Name of this value on the wire
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $wire,)*
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self> {
+                $(
+                    if s == $wire $(|| s == $alias)* {
+                        return Ok($name::$variant);
+                    }
+                )*
+                let fallback: Option<Self> = None $(.or(Some($name::$other)))?;
+                fallback.ok_or_else(|| Error::general(format!("Unknown {} {:?}", stringify!($name), s)))
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
 /// Time in force for orders
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TimeInForce {
     Day,
     Ioc,
@@ -9,8 +63,15 @@ pub enum TimeInForce {
     Gtc,
 }
 
+wire_enum!(TimeInForce {
+    Day => "ROD",
+    Ioc => "IOC",
+    Fok => "FOK",
+    Gtc => "GTC",
+});
+
 /// Order type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OrderType {
     Market,
     Limit,
@@ -18,8 +79,15 @@ pub enum OrderType {
     StopLimit,
 }
 
+wire_enum!(OrderType {
+    Market => "Market",
+    Limit => "Limit",
+    Stop => "Stop",
+    StopLimit => "StopLimit",
+});
+
 /// Price type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PriceType {
     Limit,
     Market,
@@ -27,30 +95,122 @@ pub enum PriceType {
     LimitOnClose,
 }
 
+wire_enum!(PriceType {
+    Limit => "Limit",
+    Market => "Market",
+    MarketOnClose => "MarketOnClose",
+    LimitOnClose => "LimitOnClose",
+});
+
 /// Market type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MarketType {
     Stock,
     Future,
     Option,
 }
 
+wire_enum!(MarketType {
+    Stock => "Stock",
+    Future => "Future",
+    Option => "Option",
+});
+
+/// Trading session (盤別) of a stock order.
+///
+/// Order quantities are always in shares: whole board lots in the regular and
+/// after-hours fixed price sessions, and fewer shares than a lot in the odd-lot
+/// sessions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MarketSession {
+    /// Regular session (整股), 09:00 to 13:30
+    #[default]
+    Regular,
+    /// After-hours fixed price trading (盤後定價), 14:00 to 14:30, matched at the closing price
+    AfterHoursFixedPrice,
+    /// After-hours odd-lot trading (盤後零股), 13:40 to 14:30, matched once at the close
+    AfterHoursOddLot,
+    /// Intraday odd-lot trading (盤中零股), 09:00 to 13:30, matched by call auction
+    IntradayOddLot,
+}
+
+wire_enum!(MarketSession {
+    Regular => "Common",
+    AfterHoursFixedPrice => "Fixing",
+    AfterHoursOddLot => "Odd",
+    IntradayOddLot => "IntradayOdd",
+});
+
+impl MarketSession {
+    /// Whether quantities are odd lots, below one board lot
+    pub fn is_odd_lot(&self) -> bool {
+        matches!(self, MarketSession::AfterHoursOddLot | MarketSession::IntradayOddLot)
+    }
+    
+    /// Whether orders in this session may use the given time in force; only
+    /// the regular session accepts anything other than ROD
+    pub fn allows_time_in_force(&self, time_in_force: TimeInForce) -> bool {
+        match self {
+            MarketSession::Regular => true,
+            _ => time_in_force == TimeInForce::Day,
+        }
+    }
+    
+    /// Whether orders in this session may use the given order type; only the
+    /// regular session accepts anything other than limit orders
+    pub fn allows_order_type(&self, order_type: OrderType) -> bool {
+        match self {
+            MarketSession::Regular => true,
+            _ => order_type == OrderType::Limit,
+        }
+    }
+    
+    /// Check an order quantity in shares against the session's lot rules
+    pub fn validate_quantity(&self, quantity: u32) -> Result<()> {
+        let quantity = quantity as u64;
+        if self.is_odd_lot() {
+            if quantity == 0 || quantity >= STOCK_LOT_SIZE {
+                return Err(Error::invalid_session(*self, format!(
+                    "quantity must be 1 to {} shares, got {} shares",
+                    STOCK_LOT_SIZE - 1, quantity
+                )));
+            }
+        } else if quantity == 0 || !quantity.is_multiple_of(STOCK_LOT_SIZE) {
+            return Err(Error::invalid_session(*self, format!(
+                "quantity must be a whole number of {}-share lots, got {} shares",
+                STOCK_LOT_SIZE, quantity
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// Buy/Sell action
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BSAction {
     Buy,
     Sell,
 }
 
+wire_enum!(BSAction {
+    Buy => "B",
+    Sell => "S",
+});
+
 /// Future/Option market type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FutOptMarketType {
     Future,
     Option,
 }
 
+wire_enum!(FutOptMarketType {
+    Future => "Future",
+    Option => "Option",
+});
+
 /// Future/Option order type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FutOptOrderType {
     Market,
     Limit,
@@ -58,38 +218,69 @@ pub enum FutOptOrderType {
     StopLimit,
 }
 
+wire_enum!(FutOptOrderType {
+    Market => "Market",
+    Limit => "Limit",
+    Stop => "Stop",
+    StopLimit => "StopLimit",
+});
+
 /// Future/Option price type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FutOptPriceType {
     Limit,
     Market,
 }
 
+wire_enum!(FutOptPriceType {
+    Limit => "Limit",
+    Market => "Market",
+});
+
 /// Call/Put option type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CallPut {
     Call,
     Put,
 }
 
+wire_enum!(CallPut {
+    Call => "Call",
+    Put => "Put",
+});
+
 /// Trigger content for conditional orders
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TriggerContent {
     Price,
     Volume,
 }
 
+wire_enum!(TriggerContent {
+    Price => "MatchedPrice",
+    Volume => "TotalQuantity",
+});
+
 /// Trading type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum TradingType {
+    /// Cash trading (現股)
+    #[default]
     Normal,
     DayTrade,
     Margin,
     Short,
 }
 
+wire_enum!(TradingType {
+    Normal => "Cash" | "Stock",
+    DayTrade => "DayTrade",
+    Margin => "Margin",
+    Short => "Short",
+});
+
 /// Condition operator
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Operator {
     GreaterThan,
     LessThan,
@@ -98,45 +289,80 @@ pub enum Operator {
     Equal,
 }
 
+wire_enum!(Operator {
+    GreaterThan => "GreaterThan",
+    LessThan => "LessThan",
+    GreaterEqual => "GreaterThanOrEqual",
+    LessEqual => "LessThanOrEqual",
+    Equal => "Equal",
+});
+
 /// Condition order type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConditionOrderType {
     Stop,
     StopLimit,
     Oco,
 }
 
+wire_enum!(ConditionOrderType {
+    Stop => "Stop",
+    StopLimit => "StopLimit",
+    Oco => "OCO",
+});
+
 /// Condition price type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConditionPriceType {
     Limit,
     Market,
 }
 
+wire_enum!(ConditionPriceType {
+    Limit => "Limit",
+    Market => "Market",
+});
+
 /// Condition market type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConditionMarketType {
     Stock,
     Future,
     Option,
 }
 
+wire_enum!(ConditionMarketType {
+    Stock => "Stock",
+    Future => "Future",
+    Option => "Option",
+});
+
 /// Stop sign
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StopSign {
     Up,
     Down,
 }
 
+wire_enum!(StopSign {
+    Up => "Up",
+    Down => "Down",
+});
+
 /// Direction
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     Buy,
     Sell,
 }
 
+wire_enum!(Direction {
+    Buy => "B",
+    Sell => "S",
+});
+
 /// Condition status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConditionStatus {
     Pending,
     Triggered,
@@ -144,8 +370,15 @@ pub enum ConditionStatus {
     Expired,
 }
 
+wire_enum!(ConditionStatus {
+    Pending => "Pending",
+    Triggered => "Triggered",
+    Cancelled => "Cancelled",
+    Expired => "Expired",
+});
+
 /// History status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HistoryStatus {
     Filled,
     PartiallyFilled,
@@ -153,15 +386,125 @@ pub enum HistoryStatus {
     Rejected,
 }
 
+wire_enum!(HistoryStatus {
+    Filled => "Filled",
+    PartiallyFilled => "PartiallyFilled",
+    Cancelled => "Cancelled",
+    Rejected => "Rejected",
+});
+
+impl HistoryStatus {
+    /// Whether an order with the given status belongs to this history filter
+    pub fn matches(&self, status: OrderStatus) -> bool {
+        match self {
+            HistoryStatus::Filled => status == OrderStatus::Filled,
+            HistoryStatus::PartiallyFilled => status == OrderStatus::PartiallyFilled,
+            HistoryStatus::Cancelled => status == OrderStatus::Cancelled,
+            HistoryStatus::Rejected => status == OrderStatus::Rejected,
+        }
+    }
+}
+
+/// Order status reported by the broker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    Pending,
+    Working,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Rejected,
+    /// Status not known to this SDK; treated as no longer working
+    Unknown,
+}
+
+wire_enum!(OrderStatus {
+    Pending => "Pending",
+    Working => "Working",
+    PartiallyFilled => "PartiallyFilled",
+    Filled => "Filled",
+    Cancelled => "Cancelled",
+    Rejected => "Rejected",
+    Unknown => "Unknown",
+} else Unknown);
+
+impl OrderStatus {
+    /// Whether the order can still be filled, modified or cancelled
+    pub fn is_active(&self) -> bool {
+        matches!(self, OrderStatus::Pending | OrderStatus::Working | OrderStatus::PartiallyFilled)
+    }
+}
+
 /// Order structure
+///
+/// Serialized with this crate's field names. Only the enum values match the
+/// Python `fubon_neo` package: its `Order` names the fields `buy_sell`,
+/// `market_type` (the session), `price_type` and `order_type` (cash, margin or
+/// short), so its order JSON does not deserialize into this type.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
     pub symbol: String,
+    /// Quantity in shares, see [`MarketSession`] for what each session accepts
     pub quantity: u32,
-    pub price: Option<f64>,
+    /// Limit price, for limit and stop-limit orders
+    pub price: Option<Price>,
+    /// Price that triggers stop and stop-limit orders
+    #[serde(default)]
+    pub stop_price: Option<Price>,
     pub order_type: OrderType,
     pub time_in_force: TimeInForce,
     pub action: BSAction,
+    /// Cash, margin, short or day-trade order; cash when omitted
+    #[serde(default)]
+    pub trading_type: TradingType,
+    /// Session the order trades in; the regular session when omitted
+    #[serde(default)]
+    pub session: MarketSession,
+}
+
+impl Order {
+    /// Whether the order draws on the margin or short quota: margin buys
+    /// (融資買進) and short sells (融券賣出). Closing trades do not.
+    pub fn uses_margin_quota(&self) -> bool {
+        matches!(
+            (self.trading_type, self.action),
+            (TradingType::Margin, BSAction::Buy) | (TradingType::Short, BSAction::Sell)
+        )
+    }
+    
+    /// Check the quantity, time in force and order type against the rules of the order's session
+    pub fn validate_session(&self) -> Result<()> {
+        self.session.validate_quantity(self.quantity)?;
+        
+        if !self.session.allows_time_in_force(self.time_in_force) {
+            return Err(Error::invalid_session(self.session, format!(
+                "time in force {} is not allowed, only {}",
+                self.time_in_force, TimeInForce::Day
+            )));
+        }
+        
+        if !self.session.allows_order_type(self.order_type) {
+            return Err(Error::invalid_session(self.session, format!(
+                "order type {} is not allowed, only {}",
+                self.order_type, OrderType::Limit
+            )));
+        }
+        
+        Ok(())
+    }
+    
+    /// Check the limit and stop prices against the tick ladder and, given the
+    /// day's reference price, the daily price limits. Market orders have no
+    /// price to check.
+    pub fn validate_price(&self, reference: Option<Price>) -> Result<()> {
+        if self.order_type == OrderType::Market {
+            return Ok(());
+        }
+        for price in self.price.iter().chain(&self.stop_price) {
+            crate::tick::validate_price(&self.symbol, *price, reference)?;
+        }
+        Ok(())
+    }
 }
 
 /// Condition structure  
@@ -169,7 +512,7 @@ pub struct Order {
 pub struct Condition {
     pub symbol: String,
     pub operator: Operator,
-    pub value: f64,
+    pub value: Price,
     pub trigger_content: TriggerContent,
 }
 
@@ -186,7 +529,11 @@ pub struct ConditionOrder {
 pub struct FutOptOrder {
     pub symbol: String,
     pub quantity: u32,
-    pub price: Option<f64>,
+    /// Limit price, for limit and stop-limit orders
+    pub price: Option<Price>,
+    /// Price that triggers stop and stop-limit orders
+    #[serde(default)]
+    pub stop_price: Option<Price>,
     pub order_type: FutOptOrderType,
     pub action: BSAction,
 }
@@ -198,6 +545,477 @@ pub struct FutOptConditionOrder {
     pub order: FutOptOrder,
 }
 
+/// Original request an order result belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "order", rename_all = "snake_case")]
+pub enum OrderRequest {
+    Stock(Order),
+    Condition(ConditionOrder),
+    FutOpt(FutOptOrder),
+    FutOptCondition(FutOptConditionOrder),
+}
+
+impl OrderRequest {
+    /// Symbol of the order to be placed
+    pub fn symbol(&self) -> &str {
+        match self {
+            OrderRequest::Stock(order) => &order.symbol,
+            OrderRequest::Condition(condition_order) => &condition_order.order.symbol,
+            OrderRequest::FutOpt(order) => &order.symbol,
+            OrderRequest::FutOptCondition(condition_order) => &condition_order.order.symbol,
+        }
+    }
+    
+    /// Requested quantity
+    pub fn quantity(&self) -> u32 {
+        match self {
+            OrderRequest::Stock(order) => order.quantity,
+            OrderRequest::Condition(condition_order) => condition_order.order.quantity,
+            OrderRequest::FutOpt(order) => order.quantity,
+            OrderRequest::FutOptCondition(condition_order) => condition_order.order.quantity,
+        }
+    }
+    
+    /// Buy or sell
+    pub fn action(&self) -> BSAction {
+        match self {
+            OrderRequest::Stock(order) => order.action,
+            OrderRequest::Condition(condition_order) => condition_order.order.action,
+            OrderRequest::FutOpt(order) => order.action,
+            OrderRequest::FutOptCondition(condition_order) => condition_order.order.action,
+        }
+    }
+    
+    /// Requested price (`None` for market orders)
+    pub fn price(&self) -> Option<Price> {
+        match self {
+            OrderRequest::Stock(order) => order.price,
+            OrderRequest::Condition(condition_order) => condition_order.order.price,
+            OrderRequest::FutOpt(order) => order.price,
+            OrderRequest::FutOptCondition(condition_order) => condition_order.order.price,
+        }
+    }
+    
+    /// Whether this is a market order, which has no price to modify
+    pub fn is_market(&self) -> bool {
+        match self {
+            OrderRequest::Stock(order) => order.order_type == OrderType::Market,
+            OrderRequest::Condition(condition_order) => condition_order.order.order_type == OrderType::Market,
+            OrderRequest::FutOpt(order) => order.order_type == FutOptOrderType::Market,
+            OrderRequest::FutOptCondition(condition_order) => condition_order.order.order_type == FutOptOrderType::Market,
+        }
+    }
+    
+    /// Whether this is a future/option order
+    pub fn is_futopt(&self) -> bool {
+        matches!(self, OrderRequest::FutOpt(_) | OrderRequest::FutOptCondition(_))
+    }
+    
+    /// Whether this is a conditional order that has not been sent to the exchange yet
+    pub fn is_condition(&self) -> bool {
+        matches!(self, OrderRequest::Condition(_) | OrderRequest::FutOptCondition(_))
+    }
+    
+    /// Update the requested price
+    pub fn set_price(&mut self, price: Price) {
+        match self {
+            OrderRequest::Stock(order) => order.price = Some(price),
+            OrderRequest::Condition(condition_order) => condition_order.order.price = Some(price),
+            OrderRequest::FutOpt(order) => order.price = Some(price),
+            OrderRequest::FutOptCondition(condition_order) => condition_order.order.price = Some(price),
+        }
+    }
+    
+    /// Update the requested quantity
+    pub fn set_quantity(&mut self, quantity: u32) {
+        match self {
+            OrderRequest::Stock(order) => order.quantity = quantity,
+            OrderRequest::Condition(condition_order) => condition_order.order.quantity = quantity,
+            OrderRequest::FutOpt(order) => order.quantity = quantity,
+            OrderRequest::FutOptCondition(condition_order) => condition_order.order.quantity = quantity,
+        }
+    }
+}
+
+/// Result of a placed order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderResult {
+    /// Account the order was placed in
+    #[serde(default)]
+    pub account_id: String,
+    /// Broker order number (委託書號)
+    pub order_no: String,
+    /// Order sequence number (委託序號)
+    pub seq_no: String,
+    /// Current order status
+    pub status: OrderStatus,
+    /// Filled quantity
+    pub filled_quantity: u32,
+    /// Quantity still working
+    pub remaining_quantity: u32,
+    /// Average fill price
+    pub avg_fill_price: Option<Price>,
+    /// Time the order was accepted
+    pub created_at: DateTime<Utc>,
+    /// Time of the last status change
+    pub updated_at: DateTime<Utc>,
+    /// Time of the last fill
+    pub last_filled_at: Option<DateTime<Utc>>,
+    /// Original request
+    pub request: OrderRequest,
+}
+
+impl OrderResult {
+    /// Create a result for a newly accepted order with nothing filled yet
+    pub fn new(account_id: String, order_no: String, seq_no: String, status: OrderStatus, request: OrderRequest) -> Self {
+        let now = Utc::now();
+        Self {
+            account_id,
+            order_no,
+            seq_no,
+            status,
+            filled_quantity: 0,
+            remaining_quantity: request.quantity(),
+            avg_fill_price: None,
+            created_at: now,
+            updated_at: now,
+            last_filled_at: None,
+            request,
+        }
+    }
+}
+
+/// Single execution of an order (成交回報)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillRecord {
+    /// Account the order was placed in
+    pub account_id: String,
+    /// Broker order number (委託書號)
+    pub order_no: String,
+    /// Fill sequence number (成交序號)
+    pub fill_no: String,
+    /// Stock or contract symbol
+    pub symbol: String,
+    /// Buy or sell
+    pub action: BSAction,
+    /// Filled quantity
+    pub quantity: u32,
+    /// Fill price
+    pub price: Price,
+    /// Execution time
+    pub filled_at: DateTime<Utc>,
+}
+
+/// Reference to a working order, either by order number or by a previous result
+#[derive(Debug, Clone, Copy)]
+pub enum OrderRef<'a> {
+    Id(&'a str),
+    Result(&'a OrderResult),
+}
+
+impl OrderRef<'_> {
+    /// Broker order number
+    pub fn order_no(&self) -> &str {
+        match self {
+            OrderRef::Id(order_no) => order_no,
+            OrderRef::Result(result) => &result.order_no,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for OrderRef<'a> {
+    fn from(order_no: &'a str) -> Self {
+        OrderRef::Id(order_no)
+    }
+}
+
+impl<'a> From<&'a String> for OrderRef<'a> {
+    fn from(order_no: &'a String) -> Self {
+        OrderRef::Id(order_no)
+    }
+}
+
+impl<'a> From<&'a OrderResult> for OrderRef<'a> {
+    fn from(result: &'a OrderResult) -> Self {
+        OrderRef::Result(result)
+    }
+}
+
+/// Open position in a stock inventory or future/option account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    /// Stock or contract symbol
+    pub symbol: String,
+    /// Market the position is held in
+    pub market_type: MarketType,
+    /// Direction of the position (`Buy` for long, `Sell` for short)
+    pub action: BSAction,
+    /// Cash, margin, short or day-trade position
+    pub trading_type: TradingType,
+    /// Quantity held, in shares for stocks and contracts for futures/options
+    pub quantity: u64,
+    /// Average cost per share or contract
+    pub avg_cost: Price,
+    /// Last traded price
+    pub last_price: Price,
+    /// Unrealized profit and loss at the last price
+    pub unrealized_pnl: Amount,
+    /// Quantity bought today
+    #[serde(default)]
+    pub today_buy_quantity: u64,
+    /// Quantity sold today
+    #[serde(default)]
+    pub today_sell_quantity: u64,
+}
+
+impl Position {
+    /// Whether this is a stock inventory position
+    pub fn is_stock(&self) -> bool {
+        self.market_type == MarketType::Stock
+    }
+    
+    /// Quantity in board lots (張); futures/options count each contract as a lot
+    pub fn lots(&self) -> u64 {
+        if self.is_stock() {
+            self.quantity / STOCK_LOT_SIZE
+        } else {
+            self.quantity
+        }
+    }
+    
+    /// Shares left over after whole board lots (零股)
+    pub fn odd_lot_shares(&self) -> u64 {
+        if self.is_stock() {
+            self.quantity % STOCK_LOT_SIZE
+        } else {
+            0
+        }
+    }
+}
+
+/// Kind of combined futures/options position (組合部位)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinationType {
+    /// Futures calendar spread (期貨價差)
+    FutureSpread,
+    /// Option price spread, e.g. a bull call spread (價格價差)
+    PriceSpread,
+    /// Option calendar spread (時間價差)
+    TimeSpread,
+    /// Long or short call and put at the same strike (跨式)
+    Straddle,
+    /// Long or short call and put at different strikes (勒式)
+    Strangle,
+    /// Option against futures, e.g. a conversion (期權組合)
+    Conversion,
+    /// Combination not known to this SDK
+    Other,
+}
+
+wire_enum!(CombinationType {
+    FutureSpread => "FutureSpread",
+    PriceSpread => "PriceSpread",
+    TimeSpread => "TimeSpread",
+    Straddle => "Straddle",
+    Strangle => "Strangle",
+    Conversion => "Conversion",
+    Other => "Other",
+} else Other);
+
+/// One leg of a combined futures/options position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombinedLeg {
+    /// Contract symbol
+    pub symbol: String,
+    pub market_type: MarketType,
+    /// Long (`Buy`) or short (`Sell`) leg
+    pub action: BSAction,
+    /// Contracts per combination
+    pub ratio: u32,
+    /// Average cost per contract
+    pub avg_cost: Price,
+    /// Last traded price
+    pub last_price: Price,
+}
+
+/// Futures/options legs margined together as one position (組合部位)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombinedPosition {
+    pub combination_type: CombinationType,
+    /// Number of combinations held
+    pub quantity: u64,
+    pub legs: Vec<CombinedLeg>,
+    /// Margin required for the whole combination
+    pub margin: Amount,
+    /// Unrealized profit and loss of all legs at their last prices
+    pub unrealized_pnl: Amount,
+}
+
+/// Futures/options positions, with combined positions kept apart from single legs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FutOptPositions {
+    /// Positions margined on their own (單式部位)
+    pub single: Vec<Position>,
+    /// Spread and other combined positions (組合部位)
+    #[serde(default)]
+    pub combined: Vec<CombinedPosition>,
+}
+
+impl FutOptPositions {
+    /// Unrealized profit and loss of single and combined positions
+    pub fn unrealized_pnl(&self) -> Amount {
+        self.single.iter().map(|position| position.unrealized_pnl).sum::<Amount>()
+            + self.combined.iter().map(|position| position.unrealized_pnl).sum::<Amount>()
+    }
+}
+
+/// Equity and margin of a futures/options account (權益數/保證金)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FutOptEquity {
+    #[serde(default)]
+    pub account_id: String,
+    pub currency: String,
+    /// Account equity including unrealized profit and loss (權益數)
+    pub equity: Amount,
+    /// Initial margin required by open positions (原始保證金)
+    pub initial_margin: Amount,
+    /// Maintenance margin required by open positions (維持保證金)
+    pub maintenance_margin: Amount,
+    /// Equity above the initial margin, available for new positions (超額保證金)
+    pub excess_margin: Amount,
+    /// Equity as a share of the maintenance margin (風險指標), `None` without open positions
+    #[serde(default)]
+    pub risk_indicator: Option<f64>,
+    /// Unrealized profit and loss of open positions
+    pub unrealized_pnl: Amount,
+}
+
+impl FutOptEquity {
+    /// Whether equity has fallen below the maintenance margin (追繳)
+    pub fn is_margin_call(&self) -> bool {
+        self.maintenance_margin.is_positive() && self.equity < self.maintenance_margin
+    }
+}
+
+/// Trading day of a timestamp in market local time
+pub(crate) fn trading_day(time: DateTime<Utc>) -> NaiveDate {
+    let offset = FixedOffset::east_opt(MARKET_UTC_OFFSET).expect("valid market offset");
+    time.with_timezone(&offset).date_naive()
+}
+
+/// Net settlement amount (交割款) due on one settlement day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settlement {
+    /// Day the amount is settled with the bank account
+    pub date: NaiveDate,
+    /// Net amount, positive when the account receives money and negative when it pays
+    pub amount: Amount,
+}
+
+/// Cash position of an account: bank balance, buying power and pending settlements
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Balance {
+    #[serde(default)]
+    pub account_id: String,
+    pub currency: String,
+    /// Balance of the linked settlement bank account (銀行餘額)
+    pub bank_balance: Amount,
+    /// Amount available for new buy orders today (今日可用額度)
+    pub buying_power: Amount,
+    /// Settlement due today, for trades made two trading days ago (T)
+    pub t0: Settlement,
+    /// Settlement due on the next trading day (T+1)
+    pub t1: Settlement,
+    /// Settlement due in two trading days, for today's trades (T+2)
+    pub t2: Settlement,
+}
+
+impl Balance {
+    /// Pending settlements in date order
+    pub fn settlements(&self) -> [&Settlement; 3] {
+        [&self.t0, &self.t1, &self.t2]
+    }
+    
+    /// Net of all pending settlements
+    pub fn pending_settlement(&self) -> Amount {
+        self.settlements().iter().map(|settlement| settlement.amount).sum()
+    }
+    
+    /// Bank balance once all pending settlements are done
+    pub fn projected_bank_balance(&self) -> Amount {
+        self.bank_balance + self.pending_settlement()
+    }
+    
+    /// Whether a purchase of `amount`, settled at T+2, stays within today's
+    /// buying power and leaves the bank balance positive on every settlement day
+    pub fn covers(&self, amount: Amount) -> bool {
+        if amount > self.buying_power {
+            return false;
+        }
+        
+        let mut balance = self.bank_balance;
+        for settlement in self.settlements() {
+            balance += settlement.amount;
+            if balance.is_negative() {
+                return false;
+            }
+        }
+        
+        balance >= amount
+    }
+}
+
+/// Margin purchase and short sale capacity for one symbol (融資融券額度)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginQuota {
+    pub symbol: String,
+    /// Whether the symbol may be bought on margin (可融資)
+    pub margin_available: bool,
+    /// Shares that can still be bought on margin
+    pub margin_quota: u64,
+    /// Share of the purchase price financed by the broker, e.g. 0.6 (融資成數)
+    pub margin_ratio: f64,
+    /// Whether the symbol may be sold short (可融券)
+    pub short_available: bool,
+    /// Shares that can still be sold short
+    pub short_quota: u64,
+    /// Share of the sale price posted as collateral, e.g. 0.9 (融券保證金成數)
+    pub short_ratio: f64,
+    /// Current maintenance ratio of the account (整戶維持率), `None` without margin positions
+    #[serde(default)]
+    pub maintenance_ratio: Option<f64>,
+}
+
+impl MarginQuota {
+    /// Shares available to an order of the given trading type and side, `None`
+    /// if the order does not draw on a quota
+    pub fn available_for(&self, trading_type: TradingType, action: BSAction) -> Option<u64> {
+        match (trading_type, action) {
+            (TradingType::Margin, BSAction::Buy) => Some(if self.margin_available { self.margin_quota } else { 0 }),
+            (TradingType::Short, BSAction::Sell) => Some(if self.short_available { self.short_quota } else { 0 }),
+            _ => None,
+        }
+    }
+    
+    /// Check that an order fits in the remaining quota
+    pub fn check(&self, order: &Order) -> Result<()> {
+        let Some(available) = self.available_for(order.trading_type, order.action) else {
+            return Ok(());
+        };
+        
+        let requested = order.quantity as u64;
+        if requested > available {
+            return Err(Error::InsufficientQuota {
+                symbol: order.symbol.clone(),
+                trading_type: order.trading_type,
+                requested,
+                available,
+            });
+        }
+        Ok(())
+    }
+}
+
 /// Account information returned from login
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
@@ -212,9 +1030,23 @@ pub struct Account {
     /// Currency
     pub currency: String,
     /// Available balance
-    pub available_balance: Option<f64>,
+    pub available_balance: Option<Amount>,
     /// Total balance
-    pub total_balance: Option<f64>,
+    pub total_balance: Option<Amount>,
+}
+
+impl Account {
+    /// Whether this is a securities account for stock orders
+    pub fn is_stock(&self) -> bool {
+        self.account_type.eq_ignore_ascii_case("stock")
+    }
+    
+    /// Whether this is a futures/options account
+    pub fn is_futopt(&self) -> bool {
+        ["future", "futures", "option", "options", "futopt"]
+            .iter()
+            .any(|kind| self.account_type.eq_ignore_ascii_case(kind))
+    }
 }
 
 /// Login credentials structure